@@ -0,0 +1,376 @@
+use aoc_common::error::parse_token;
+use aoc_common::{Answer, ParseError, Solution};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::io::{self, BufRead};
+use std::mem;
+
+pub mod report;
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    type Parsed = Vec<Vec<usize>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed).into()
+    }
+}
+
+/// One elf's items per group of lines, the groups separated by blank lines. Lines may end in
+/// CRLF and carry whitespace around the number, and whitespace-only lines are blank. A run of
+/// blank lines separates two elves like a single one, and blank lines before the first or
+/// after the last elf are ignored, so every elf has at least one item.
+fn parse_input(input: &str) -> Result<Vec<Vec<usize>>, ParseError> {
+    let mut elves: Vec<Vec<usize>> = Vec::new();
+    let mut items: Vec<usize> = Vec::new();
+    let mut total: usize = 0;
+    for (idx, line) in input.lines().enumerate() {
+        let entry = line.trim();
+        if entry.is_empty() {
+            if !items.is_empty() {
+                elves.push(mem::take(&mut items));
+            }
+            continue;
+        }
+        items.push(parse_calories(
+            idx + 1,
+            line,
+            entry,
+            elves.len(),
+            &mut total,
+        )?);
+    }
+    if !items.is_empty() {
+        elves.push(items);
+    }
+    Ok(elves)
+}
+
+/// The calories of one item, `entry` being `line` without surrounding whitespace. Errors name
+/// the index of the `elf` the item belongs to, counting from 0 like [`Elf::index`]. `total` is
+/// what the inventory carries so far; it has to stay a `usize` so that no sum of elves
+/// overflows.
+fn parse_calories(
+    line_no: usize,
+    line: &str,
+    entry: &str,
+    elf: usize,
+    total: &mut usize,
+) -> Result<usize, ParseError> {
+    let calories: usize = parse_token(
+        line_no,
+        line,
+        entry,
+        &format!("a calorie count for the elf at index {}", elf),
+    )?;
+    let Some(sum) = total.checked_add(calories) else {
+        return Err(ParseError::at(
+            line_no,
+            line,
+            entry,
+            &format!(
+                "a calorie count that keeps the inventory within {} calories",
+                usize::MAX
+            ),
+        ));
+    };
+    *total = sum;
+    Ok(calories)
+}
+
+/// An elf by its position in the inventory, counting from 0, and the calories it carries.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub struct Elf {
+    pub index: usize,
+    pub calories: usize,
+}
+
+/// The `k` elves carrying the most calories, most first, out of the total calories of every
+/// elf in inventory order. Ties go to the earlier elf. Only `k` elves are kept at a time, so
+/// `totals` can be as long as it likes.
+pub fn top_k<I: IntoIterator<Item = usize>>(totals: I, k: usize) -> Vec<Elf> {
+    // a min-heap of the best elves so far, whose root is the first to be pushed out
+    let mut heap: BinaryHeap<Reverse<(usize, Reverse<usize>)>> = BinaryHeap::with_capacity(k + 1);
+    for (index, calories) in totals.into_iter().enumerate() {
+        heap.push(Reverse((calories, Reverse(index))));
+        if heap.len() > k {
+            heap.pop();
+        }
+    }
+    let mut top: Vec<Elf> = heap
+        .into_iter()
+        .map(|Reverse((calories, Reverse(index)))| Elf { index, calories })
+        .collect();
+    top.sort_unstable_by(|a, b| b.calories.cmp(&a.calories).then(a.index.cmp(&b.index)));
+    top
+}
+
+/// [`top_k`] of an inventory read line by line, see [`Totals`].
+pub fn read_top_k<R: BufRead>(reader: R, k: usize) -> io::Result<Vec<Elf>> {
+    let mut error: Option<io::Error> = None;
+    let top = top_k(
+        Totals::new(reader).map_while(|total| match total {
+            Ok(total) => Some(total),
+            Err(e) => {
+                error = Some(e);
+                None
+            }
+        }),
+        k,
+    );
+    match error {
+        Some(e) => Err(e),
+        None => Ok(top),
+    }
+}
+
+/// The total calories of every elf in an inventory, read one line at a time without holding
+/// on to the items. Follows the puzzle input format like [`Day01::parse`]; a line that isn't a
+/// calorie count ends the iteration with an [`io::ErrorKind::InvalidData`] error wrapping the
+/// [`ParseError`].
+pub struct Totals<R> {
+    lines: io::Lines<R>,
+    line_no: usize,
+    /// Elves read so far.
+    elves: usize,
+    /// The calories of all elves read so far.
+    calories: usize,
+    done: bool,
+}
+
+impl<R: BufRead> Totals<R> {
+    pub fn new(reader: R) -> Totals<R> {
+        Totals {
+            lines: reader.lines(),
+            line_no: 0,
+            elves: 0,
+            calories: 0,
+            done: false,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for Totals<R> {
+    type Item = io::Result<usize>;
+
+    fn next(&mut self) -> Option<io::Result<usize>> {
+        if self.done {
+            return None;
+        }
+        let mut total: usize = 0;
+        // whether the current elf has any items yet, as blank lines before it don't count
+        let mut started = false;
+        loop {
+            let line = match self.lines.next() {
+                Some(Ok(line)) => line,
+                Some(Err(e)) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+                // the last elf doesn't need a blank line after it
+                None => {
+                    self.done = true;
+                    return started.then_some(Ok(total));
+                }
+            };
+            self.line_no += 1;
+            let entry = line.trim();
+            if entry.is_empty() {
+                if !started {
+                    continue;
+                }
+                self.elves += 1;
+                return Some(Ok(total));
+            }
+            started = true;
+            match parse_calories(self.line_no, &line, entry, self.elves, &mut self.calories) {
+                Ok(calories) => total += calories,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(io::Error::new(io::ErrorKind::InvalidData, e)));
+                }
+            }
+        }
+    }
+}
+
+/// The total calories of every elf, in inventory order.
+fn totals(input: &[Vec<usize>]) -> impl Iterator<Item = usize> + '_ {
+    input.iter().map(|elf| elf.iter().sum())
+}
+
+#[allow(clippy::needless_return, clippy::ptr_arg)]
+fn part1(input: &Vec<Vec<usize>>) -> usize {
+    return top_k(totals(input), 1).iter().map(|elf| elf.calories).sum();
+}
+
+#[allow(clippy::needless_return, clippy::ptr_arg)]
+fn part2(input: &Vec<Vec<usize>>) -> usize {
+    return top_k(totals(input), 3).iter().map(|elf| elf.calories).sum();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::input;
+    use proptest::prelude::*;
+
+    fn elves() -> impl Strategy<Value = Vec<Vec<usize>>> {
+        prop::collection::vec(prop::collection::vec(0..100_000usize, 1..8), 1..20)
+    }
+
+    fn to_input(elves: &[Vec<usize>]) -> String {
+        elves
+            .iter()
+            .map(|elf| {
+                elf.iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n")
+    }
+
+    fn elf(index: usize, calories: usize) -> Elf {
+        Elf { index, calories }
+    }
+
+    #[test]
+    fn test_top_k() {
+        let totals = [5, 9, 5, 1, 9];
+        assert_eq!(top_k(totals, 3), vec![elf(1, 9), elf(4, 9), elf(0, 5)]);
+        assert_eq!(top_k(totals, 0), vec![]);
+        assert_eq!(top_k(totals, 10).len(), 5);
+        assert_eq!(top_k([], 3), vec![]);
+    }
+
+    #[test]
+    fn test_read_top_k() {
+        let sample = input::read_input("sample").unwrap();
+        assert_eq!(
+            read_top_k(sample.as_bytes(), 3).unwrap(),
+            vec![elf(3, 24000), elf(2, 11000), elf(4, 10000)]
+        );
+
+        let error = read_top_k("1\n\n2\nx\n".as_bytes(), 1).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(
+            error
+                .into_inner()
+                .unwrap()
+                .downcast::<ParseError>()
+                .unwrap(),
+            Box::new(ParseError::new(
+                4,
+                1,
+                "x",
+                "a calorie count for the elf at index 1"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_tolerant_parsing() {
+        assert_eq!(
+            parse_input("1000\r\n 2000 \r\n\r\n3000\t\n").unwrap(),
+            vec![vec![1000, 2000], vec![3000]]
+        );
+        // a run of blank lines is one separator, and blank lines around the elves don't count
+        assert_eq!(
+            parse_input("\n1000\n\n \r\n\n3000\n\n").unwrap(),
+            vec![vec![1000], vec![3000]]
+        );
+        assert_eq!(parse_input("1000\n\n").unwrap(), vec![vec![1000]]);
+        assert_eq!(parse_input("\n \n").unwrap(), Vec::<Vec<usize>>::new());
+        assert_eq!(parse_input("").unwrap(), Vec::<Vec<usize>>::new());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_input("1\n\n2\n3x\n").unwrap_err(),
+            ParseError::new(4, 1, "3x", "a calorie count for the elf at index 1")
+        );
+        assert_eq!(
+            parse_input("1\n\n\n\n  -5\r\n").unwrap_err(),
+            ParseError::new(5, 3, "-5", "a calorie count for the elf at index 1")
+        );
+        assert_eq!(
+            parse_input("1 2").unwrap_err(),
+            ParseError::new(1, 1, "1 2", "a calorie count for the elf at index 0")
+        );
+        let max = usize::MAX.to_string();
+        assert_eq!(
+            parse_input(&format!("1\n\n{}", max)).unwrap_err(),
+            ParseError::new(
+                3,
+                1,
+                &max,
+                &format!(
+                    "a calorie count that keeps the inventory within {} calories",
+                    max
+                )
+            )
+        );
+    }
+
+    #[test]
+    fn test_totals_follow_the_parser() {
+        for inventory in ["", "1\n2\n", "1\n\n", "\n\n1\r\n\n \n\n3\n 4 \n\n"] {
+            let streamed: Vec<usize> = Totals::new(inventory.as_bytes())
+                .map(|t| t.unwrap())
+                .collect();
+            let parsed: Vec<usize> = totals(&parse_input(inventory).unwrap()).collect();
+            assert_eq!(streamed, parsed, "{:?}", inventory);
+        }
+        let overflowing = format!("1\n\n{}", usize::MAX);
+        for malformed in ["x", "1\n\n\n2\n3x", "1\n\n  -5", &overflowing] {
+            let expected = parse_input(malformed).unwrap_err();
+            let error = Totals::new(malformed.as_bytes())
+                .find_map(|t| t.err())
+                .unwrap();
+            let error = error
+                .into_inner()
+                .unwrap()
+                .downcast::<ParseError>()
+                .unwrap();
+            assert_eq!(*error, expected, "{:?}", malformed);
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parse_round_trips(elves in elves()) {
+            prop_assert_eq!(parse_input(&to_input(&elves)).unwrap(), elves);
+        }
+
+        #[test]
+        fn prop_top_three_at_least_max(elves in elves()) {
+            let total: usize = elves.iter().flatten().sum();
+            prop_assert!(part2(&elves) >= part1(&elves));
+            prop_assert!(part2(&elves) <= total);
+        }
+
+        #[test]
+        fn prop_read_top_k_matches_sorting(elves in elves(), k in 0..25usize) {
+            let mut sorted: Vec<Elf> = totals(&elves)
+                .enumerate()
+                .map(|(index, calories)| Elf { index, calories })
+                .collect();
+            sorted.sort_by(|a, b| b.calories.cmp(&a.calories).then(a.index.cmp(&b.index)));
+            sorted.truncate(k);
+            prop_assert_eq!(read_top_k(to_input(&elves).as_bytes(), k).unwrap(), sorted);
+        }
+    }
+}
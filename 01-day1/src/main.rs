@@ -1,28 +1,103 @@
-use std::fs;
+use aoc_common::input::InputSource;
+use aoc_common::output::Format;
+use aoc_common::{solution, Solution};
+use day01::report::Report;
+use day01::Day01;
+use std::env;
+use std::process::ExitCode;
 
-fn main() {
-    let input: Vec<String> = fs::read_to_string("input")
-        .unwrap()
-        .split("\n\n")
-        .map(|l| l.to_string())
-        .collect();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
+const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+const USAGE: &str =
+    "usage: day01 --top K [input|-]\n       day01 --report [--format text|json] [input|-]";
+
+/// What the binary does besides solving both parts.
+enum Mode {
+    /// The K elves carrying the most calories, streamed from the input so it can be larger
+    /// than memory.
+    Top(usize),
+    /// Statistics over all elves, see [`Report`].
+    Report(Format),
 }
 
-fn part1(input: &Vec<String>) -> usize {
-    let mut cals: Vec<usize> = Vec::new();
-    for elf in input {
-        cals.push(elf.lines().map(|x| x.parse::<usize>().unwrap()).sum());
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.iter().any(|a| a == "--top" || a == "--report") {
+        return solution::main(&Day01, DEFAULT_INPUT);
+    }
+    let (mode, input) = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let source = InputSource::resolve(input.as_deref(), DEFAULT_INPUT);
+    let result = match mode {
+        Mode::Top(k) => top(&source, k),
+        Mode::Report(format) => report(&source, format),
+    };
+    match result {
+        Ok(out) => {
+            print!("{}", out);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
     }
-    return *cals.iter().max().unwrap();
 }
 
-fn part2(input: &Vec<String>) -> usize {
-    let mut cals: Vec<usize> = Vec::new();
-    for elf in input {
-        cals.push(elf.lines().map(|x| x.parse::<usize>().unwrap()).sum());
+fn parse_args(args: &[String]) -> Result<(Mode, Option<String>), String> {
+    let mut top: Option<usize> = None;
+    let mut report = false;
+    let mut format: Option<Format> = None;
+    let mut input: Option<String> = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--top" => {
+                let k = args.next().and_then(|k| k.parse().ok());
+                top = Some(k.ok_or("--top needs a number of elves")?);
+            }
+            "--report" => report = true,
+            "--format" => format = Some(args.next().map(|f| f.as_str()).unwrap_or("").parse()?),
+            a if a.starts_with('-') && a != "-" => return Err(format!("unknown option `{}`", a)),
+            _ if input.is_none() => input = Some(arg.clone()),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        }
     }
-    cals.sort_unstable_by(|a, b| b.cmp(a));
-    return cals[0] + cals[1] + cals[2];
+    let mode = match (top, report) {
+        (Some(_), true) => return Err("--top and --report can't be combined".to_string()),
+        (Some(_), false) if format.is_some() => {
+            return Err("--format only applies to --report".to_string())
+        }
+        (Some(k), false) => Mode::Top(k),
+        (None, _) => Mode::Report(format.unwrap_or_default()),
+    };
+    Ok((mode, input))
+}
+
+fn top(source: &InputSource, k: usize) -> Result<String, String> {
+    let elves = source
+        .open()
+        .and_then(|reader| day01::read_top_k(reader, k))
+        .map_err(|e| format!("Could not read {}: {}", source, e))?;
+    Ok(elves
+        .iter()
+        .map(|elf| format!("Elf {}: {}\n", elf.index, elf.calories))
+        .collect())
+}
+
+fn report(source: &InputSource, format: Format) -> Result<String, String> {
+    let input = source
+        .read()
+        .map_err(|e| format!("Could not read {}: {}", source, e))?;
+    let inventory =
+        Day01::parse(&input).map_err(|e| format!("Could not parse {}: {}", source, e))?;
+    let report = Report::new(&inventory);
+    Ok(match format {
+        Format::Text => report.to_table(),
+        Format::Json => report.to_json() + "\n",
+    })
 }
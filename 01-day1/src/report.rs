@@ -0,0 +1,268 @@
+//! Statistics over the calories the elves carry, beyond the most and the top three.
+
+use crate::{totals, Elf};
+use serde::Serialize;
+use std::fmt::Write;
+
+/// The percentiles a report lists.
+pub const PERCENTILES: [u8; 7] = [1, 10, 25, 50, 75, 90, 99];
+
+/// About how many buckets the histogram has; bucket widths are rounded to 1, 2 or 5 times a
+/// power of ten, which can make it a few more or less.
+pub const BUCKETS: usize = 10;
+
+/// The distribution of calories over the elves of an inventory.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Report {
+    /// Every elf in inventory order.
+    pub elves: Vec<Elf>,
+    pub total: usize,
+    pub mean: f64,
+    pub median: f64,
+    pub percentiles: Vec<Percentile>,
+    pub histogram: Vec<Bucket>,
+    /// Indices of the elves carrying no calories, whether they have no items or only items of
+    /// 0 calories.
+    pub empty: Vec<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Percentile {
+    pub p: u8,
+    pub calories: f64,
+}
+
+/// The number of elves carrying from `from` up to but not including `to` calories.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Bucket {
+    pub from: usize,
+    pub to: usize,
+    pub elves: usize,
+}
+
+impl Report {
+    pub fn new(inventory: &[Vec<usize>]) -> Report {
+        let elves: Vec<Elf> = totals(inventory)
+            .enumerate()
+            .map(|(index, calories)| Elf { index, calories })
+            .collect();
+        let mut sorted: Vec<usize> = elves.iter().map(|elf| elf.calories).collect();
+        sorted.sort_unstable();
+        let total: usize = sorted.iter().sum();
+        Report {
+            total,
+            mean: if sorted.is_empty() {
+                0.0
+            } else {
+                total as f64 / sorted.len() as f64
+            },
+            median: percentile(&sorted, 50),
+            percentiles: PERCENTILES
+                .iter()
+                .map(|&p| Percentile {
+                    p,
+                    calories: percentile(&sorted, p),
+                })
+                .collect(),
+            histogram: histogram(&sorted),
+            empty: elves
+                .iter()
+                .filter(|elf| elf.calories == 0)
+                .map(|elf| elf.index)
+                .collect(),
+            elves,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    /// The report as aligned tables, the histogram with bars of up to 40 `#`.
+    pub fn to_table(&self) -> String {
+        let mut out = String::new();
+        writeln!(out, "Elves        {:>10}", self.elves.len()).unwrap();
+        writeln!(out, "Calories     {:>10}", self.total).unwrap();
+        writeln!(out, "Mean         {:>12.1}", self.mean).unwrap();
+        writeln!(out, "Median       {:>12.1}", self.median).unwrap();
+        for p in &self.percentiles {
+            writeln!(out, "{:<13}{:>12.1}", format!("p{}", p.p), p.calories).unwrap();
+        }
+        let empty: Vec<String> = self.empty.iter().map(|i| i.to_string()).collect();
+        writeln!(
+            out,
+            "Empty        {}",
+            if empty.is_empty() {
+                "none".to_string()
+            } else {
+                empty.join(", ")
+            }
+        )
+        .unwrap();
+
+        writeln!(out, "\nCalories            Elves").unwrap();
+        let most = self.histogram.iter().map(|b| b.elves).max().unwrap_or(0);
+        for bucket in &self.histogram {
+            let bar = (bucket.elves * 40).div_ceil(most.max(1));
+            let line = format!(
+                "{:>8} - {:<8} {:>5} {}",
+                bucket.from,
+                bucket.to,
+                bucket.elves,
+                "#".repeat(bar)
+            );
+            writeln!(out, "{}", line.trim_end()).unwrap();
+        }
+
+        writeln!(out, "\n  Elf  Calories").unwrap();
+        for elf in &self.elves {
+            writeln!(out, "{:>5}  {:>8}", elf.index, elf.calories).unwrap();
+        }
+        out
+    }
+}
+
+/// The `p`th percentile of `sorted`, interpolating linearly between the two closest values.
+fn percentile(sorted: &[usize], p: u8) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = p.min(100) as f64 / 100.0 * (sorted.len() - 1) as f64;
+    let (lo, hi) = (rank.floor() as usize, rank.ceil() as usize);
+    let fraction = rank - lo as f64;
+    sorted[lo] as f64 + (sorted[hi] as f64 - sorted[lo] as f64) * fraction
+}
+
+/// Buckets of equal, round width from the lowest to the highest value of `sorted`.
+fn histogram(sorted: &[usize]) -> Vec<Bucket> {
+    let (Some(&min), Some(&max)) = (sorted.first(), sorted.last()) else {
+        return Vec::new();
+    };
+    let width = round_width((max - min).div_ceil(BUCKETS));
+    let first = min / width;
+    (first..=max / width)
+        .map(|i| {
+            let (from, to) = (i * width, (i + 1) * width);
+            Bucket {
+                from,
+                to,
+                elves: sorted.iter().filter(|&&c| from <= c && c < to).count(),
+            }
+        })
+        .collect()
+}
+
+/// The smallest 1, 2 or 5 times a power of ten that is at least `width` (and at least 1).
+fn round_width(width: usize) -> usize {
+    let mut magnitude: usize = 1;
+    loop {
+        for step in [1, 2, 5] {
+            if step * magnitude >= width {
+                return step * magnitude;
+            }
+        }
+        magnitude *= 10;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_input;
+    use aoc_common::input;
+
+    fn sample() -> Report {
+        Report::new(&parse_input(&input::read_input("sample").unwrap()).unwrap())
+    }
+
+    #[test]
+    fn test_statistics() {
+        let report = sample();
+        assert_eq!(report.elves.len(), 5);
+        assert_eq!(
+            report.elves[3],
+            Elf {
+                index: 3,
+                calories: 24000
+            }
+        );
+        assert_eq!(report.total, 55000);
+        assert_eq!(report.mean, 11000.0);
+        assert_eq!(report.median, 10000.0);
+        let p = |p: u8| {
+            report
+                .percentiles
+                .iter()
+                .find(|x| x.p == p)
+                .unwrap()
+                .calories
+        };
+        assert_eq!(p(25), 6000.0);
+        assert_eq!(p(75), 11000.0);
+        assert_eq!(p(90), 18800.0);
+        assert!(report.empty.is_empty());
+    }
+
+    #[test]
+    fn test_histogram() {
+        let report = sample();
+        let counts: Vec<(usize, usize)> =
+            report.histogram.iter().map(|b| (b.from, b.elves)).collect();
+        assert_eq!(
+            counts,
+            vec![
+                (4000, 1),
+                (6000, 1),
+                (8000, 0),
+                (10000, 2),
+                (12000, 0),
+                (14000, 0),
+                (16000, 0),
+                (18000, 0),
+                (20000, 0),
+                (22000, 0),
+                (24000, 1)
+            ]
+        );
+        assert_eq!(report.histogram.iter().map(|b| b.elves).sum::<usize>(), 5);
+        assert_eq!(
+            histogram(&[7, 7]),
+            vec![Bucket {
+                from: 7,
+                to: 8,
+                elves: 2
+            }]
+        );
+        assert_eq!(round_width(0), 1);
+        assert_eq!(round_width(3), 5);
+        assert_eq!(round_width(2001), 5000);
+    }
+
+    #[test]
+    fn test_empty_elves() {
+        let report = Report::new(&parse_input("100\n\n0\n0\n\n\n200").unwrap());
+        assert_eq!(report.empty, vec![1]);
+        assert_eq!(report.median, 100.0);
+        assert!(report.to_table().contains("Empty        1\n"));
+        // an inventory built without the parser can have elves without items
+        let report = Report::new(&[vec![100], vec![]]);
+        assert_eq!(report.empty, vec![1]);
+        assert_eq!(report.median, 50.0);
+    }
+
+    #[test]
+    fn test_formats() {
+        let report = sample();
+        let table = report.to_table();
+        assert!(table.starts_with("Elves                 5\nCalories          55000\n"));
+        assert!(table.contains("    4000 - 6000         1 ####################\n"));
+        assert!(table.contains("    8000 - 10000        0\n"));
+        assert!(table.contains("\n    3     24000\n"));
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json()).unwrap();
+        assert_eq!(json["median"], 10000.0);
+        assert_eq!(json["elves"][3]["calories"], 24000);
+        assert_eq!(json["histogram"][0]["to"], 6000);
+        assert_eq!(json["percentiles"][2]["p"], 25);
+    }
+}
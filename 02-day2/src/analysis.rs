@@ -0,0 +1,368 @@
+//! What a guide's opponent column allows: the best and worst scores we could reach, what
+//! playing at random scores on average, and the best guide that keeps the outcomes within
+//! limits, like winning at most a few rounds so as not to look suspicious.
+
+use crate::rules::{Outcome, Rules, Shape};
+use crate::Guide;
+
+/// Scores the opponent's moves allow, whatever our column says.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Analysis {
+    pub best: isize,
+    pub worst: isize,
+    /// The mean score of picking every shape with the same chance.
+    pub expected: f64,
+}
+
+impl Analysis {
+    pub fn new(guide: &Guide) -> Analysis {
+        let rules = &guide.rules;
+        let shapes = rules.shapes().count() as f64;
+        let mut analysis = Analysis {
+            best: 0,
+            worst: 0,
+            expected: 0.0,
+        };
+        for round in &guide.rounds {
+            let scores: Vec<isize> = rules
+                .shapes()
+                .map(|me| rules.score(me, round.opponent))
+                .collect();
+            analysis.best += scores.iter().max().unwrap();
+            analysis.worst += scores.iter().min().unwrap();
+            analysis.expected += scores.iter().sum::<isize>() as f64 / shapes;
+        }
+        analysis
+    }
+}
+
+/// Bounds on how many rounds end with each outcome, indexed like [`Outcome::ALL`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Limits {
+    pub at_least: [usize; 3],
+    pub at_most: [usize; 3],
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            at_least: [0; 3],
+            at_most: [usize::MAX; 3],
+        }
+    }
+}
+
+impl Limits {
+    pub fn at_least(mut self, outcome: Outcome, rounds: usize) -> Limits {
+        self.at_least[outcome as usize] = rounds;
+        self
+    }
+
+    pub fn at_most(mut self, outcome: Outcome, rounds: usize) -> Limits {
+        self.at_most[outcome as usize] = rounds;
+        self
+    }
+}
+
+/// The shapes to play in every round of `guide` for the highest score whose outcomes are
+/// within `limits`.
+///
+/// A round's score only depends on the opponent's shape and on our outcome, playing the
+/// highest scoring shape that reaches it. That makes choosing outcomes a transport problem
+/// from the opponent's shapes, each with as many rounds as it is played, to the outcomes,
+/// each taking rounds within its limits, solved exactly as a minimum cost flow.
+pub fn optimize(guide: &Guide, limits: &Limits) -> Result<Vec<Shape>, String> {
+    let rules = &guide.rules;
+    let shapes: Vec<Shape> = rules.shapes().collect();
+    let mut played: Vec<usize> = vec![0; shapes.len()];
+    for round in &guide.rounds {
+        played[round.opponent.0] += 1;
+    }
+    let choice: Vec<[Option<Shape>; 3]> = shapes
+        .iter()
+        .map(|&opponent| Outcome::ALL.map(|outcome| best_response(rules, opponent, outcome)))
+        .collect();
+
+    // source, one node per opponent shape, one per outcome, sink
+    let outcome_node = |o: usize| 1 + shapes.len() + o;
+    let sink = 1 + shapes.len() + 3;
+    let mut network = Network::new(sink + 1);
+    let most = choice
+        .iter()
+        .zip(&shapes)
+        .flat_map(|(shapes, &opponent)| {
+            shapes
+                .iter()
+                .flatten()
+                .map(move |&me| rules.score(me, opponent).abs())
+        })
+        .max()
+        .unwrap_or(0);
+    // cheaper than any other way of routing the rounds, so lower limits are met if they can be
+    let forced = -(1 + 2 * guide.rounds.len() as i64 * most as i64);
+    let mut edges: Vec<Vec<Option<usize>>> = Vec::new();
+    for (s, &opponent) in shapes.iter().enumerate() {
+        network.add(0, 1 + s, played[s], 0);
+        edges.push(
+            (0..3)
+                .map(|o| {
+                    let me = choice[s][o]?;
+                    let score = rules.score(me, opponent) as i64;
+                    Some(network.add(1 + s, outcome_node(o), usize::MAX, -score))
+                })
+                .collect(),
+        );
+    }
+    let mut lower: Vec<usize> = Vec::new();
+    for o in 0..3 {
+        if limits.at_least[o] > limits.at_most[o] {
+            return Err(format!(
+                "at least {} and at most {} {}s can't both hold",
+                limits.at_least[o],
+                limits.at_most[o],
+                Outcome::ALL[o].name()
+            ));
+        }
+        lower.push(network.add(outcome_node(o), sink, limits.at_least[o], forced));
+        network.add(
+            outcome_node(o),
+            sink,
+            limits.at_most[o] - limits.at_least[o],
+            0,
+        );
+    }
+
+    let flow = network.flow(0, sink);
+    let met = (0..3).all(|o| network.flow_on(lower[o]) == limits.at_least[o]);
+    if flow < guide.rounds.len() || !met {
+        return Err("no guide keeps the outcomes within these limits".to_string());
+    }
+    let mut left: Vec<Vec<usize>> = edges
+        .iter()
+        .map(|e| {
+            e.iter()
+                .map(|e| e.map_or(0, |e| network.flow_on(e)))
+                .collect()
+        })
+        .collect();
+    Ok(guide
+        .rounds
+        .iter()
+        .map(|round| {
+            let s = round.opponent.0;
+            let o = (0..3).find(|&o| left[s][o] > 0).unwrap();
+            left[s][o] -= 1;
+            choice[s][o].unwrap()
+        })
+        .collect())
+}
+
+/// Our total score playing `shapes` in the rounds of `guide`.
+pub fn total(guide: &Guide, shapes: &[Shape]) -> isize {
+    shapes
+        .iter()
+        .zip(&guide.rounds)
+        .map(|(&me, round)| guide.rules.score(me, round.opponent))
+        .sum()
+}
+
+/// `guide` rewritten to play `shapes`, with our column read as shapes.
+pub fn to_guide(guide: &Guide, shapes: &[Shape]) -> String {
+    shapes
+        .iter()
+        .zip(&guide.rounds)
+        .map(|(&me, round)| {
+            format!(
+                "{} {}\n",
+                guide.rules.opponent_code(round.opponent),
+                guide.rules.response_code(me)
+            )
+        })
+        .collect()
+}
+
+/// The highest scoring shape that ends a round against `opponent` with `outcome`.
+fn best_response(rules: &Rules, opponent: Shape, outcome: Outcome) -> Option<Shape> {
+    rules
+        .shapes()
+        .filter(|&me| rules.outcome(me, opponent) == outcome)
+        .max_by_key(|&me| (rules.score(me, opponent), std::cmp::Reverse(me)))
+}
+
+struct Edge {
+    to: usize,
+    capacity: usize,
+    cost: i64,
+}
+
+/// A flow network small enough for successive shortest paths with Bellman-Ford. Every edge is
+/// stored next to its residual twin, so `e ^ 1` is the reverse of edge `e`.
+struct Network {
+    edges: Vec<Edge>,
+    from: Vec<usize>,
+    nodes: usize,
+}
+
+impl Network {
+    fn new(nodes: usize) -> Network {
+        Network {
+            edges: Vec::new(),
+            from: Vec::new(),
+            nodes,
+        }
+    }
+
+    fn add(&mut self, from: usize, to: usize, capacity: usize, cost: i64) -> usize {
+        self.edges.push(Edge { to, capacity, cost });
+        self.from.push(from);
+        self.edges.push(Edge {
+            to: from,
+            capacity: 0,
+            cost: -cost,
+        });
+        self.from.push(to);
+        self.edges.len() - 2
+    }
+
+    fn flow_on(&self, edge: usize) -> usize {
+        self.edges[edge ^ 1].capacity
+    }
+
+    /// Pushes as much flow as fits from `source` to `sink`, always along the cheapest path
+    /// left, and returns how much it pushed.
+    fn flow(&mut self, source: usize, sink: usize) -> usize {
+        let mut total = 0;
+        loop {
+            let mut distance: Vec<Option<i64>> = vec![None; self.nodes];
+            let mut via: Vec<Option<usize>> = vec![None; self.nodes];
+            distance[source] = Some(0);
+            for _ in 0..self.nodes {
+                let mut changed = false;
+                for (e, edge) in self.edges.iter().enumerate() {
+                    let Some(d) = distance[self.from[e]] else {
+                        continue;
+                    };
+                    if edge.capacity > 0 && distance[edge.to].is_none_or(|t| d + edge.cost < t) {
+                        distance[edge.to] = Some(d + edge.cost);
+                        via[edge.to] = Some(e);
+                        changed = true;
+                    }
+                }
+                if !changed {
+                    break;
+                }
+            }
+            if distance[sink].is_none() {
+                return total;
+            }
+            let mut path: Vec<usize> = Vec::new();
+            let mut node = sink;
+            while let Some(e) = via[node] {
+                path.push(e);
+                node = self.from[e];
+            }
+            let amount = path.iter().map(|&e| self.edges[e].capacity).min().unwrap();
+            for &e in &path {
+                self.edges[e].capacity -= amount;
+                self.edges[e ^ 1].capacity = self.edges[e ^ 1].capacity.saturating_add(amount);
+            }
+            total += amount;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reading::Reading;
+    use aoc_common::input;
+
+    fn sample() -> Guide {
+        Guide::parse(&input::read_input("sample").unwrap(), Rules::standard()).unwrap()
+    }
+
+    fn outcomes(guide: &Guide, shapes: &[Shape]) -> [usize; 3] {
+        let mut counts = [0; 3];
+        for (&me, round) in shapes.iter().zip(&guide.rounds) {
+            counts[guide.rules.outcome(me, round.opponent) as usize] += 1;
+        }
+        counts
+    }
+
+    #[test]
+    fn test_analysis() {
+        let analysis = Analysis::new(&sample());
+        // paper, scissors and rock win every round
+        assert_eq!(analysis.best, 8 + 9 + 7);
+        // scissors, rock and paper lose every round
+        assert_eq!(analysis.worst, 3 + 1 + 2);
+        // every shape's score sums to 15 over the three answers to each opponent shape
+        assert_eq!(analysis.expected, 15.0);
+    }
+
+    #[test]
+    fn test_optimize() {
+        let guide = sample();
+        let best = optimize(&guide, &Limits::default()).unwrap();
+        assert_eq!(total(&guide, &best), 24);
+        let rewritten = to_guide(&guide, &best);
+        assert_eq!(rewritten, "A Y\nB Z\nC X\n");
+        let rewritten = Guide::parse(&rewritten, Rules::standard()).unwrap();
+        assert_eq!(rewritten.score(&Reading::Shapes), Ok(24));
+
+        let limits = Limits::default().at_most(Outcome::Win, 1);
+        let shapes = optimize(&guide, &limits).unwrap();
+        assert_eq!(outcomes(&guide, &shapes)[Outcome::Win as usize], 1);
+        // win against rock with paper, draw against paper and scissors
+        assert_eq!(total(&guide, &shapes), 8 + 5 + 6);
+
+        let limits = Limits::default()
+            .at_most(Outcome::Win, 0)
+            .at_least(Outcome::Loss, 2);
+        let shapes = optimize(&guide, &limits).unwrap();
+        assert_eq!(outcomes(&guide, &shapes), [2, 1, 0]);
+        // lose against rock with scissors and against paper with rock, draw against scissors
+        assert_eq!(total(&guide, &shapes), 3 + 1 + 6);
+
+        let limits = Limits::default()
+            .at_most(Outcome::Draw, 0)
+            .at_most(Outcome::Win, 1)
+            .at_most(Outcome::Loss, 1);
+        assert_eq!(
+            optimize(&guide, &limits),
+            Err("no guide keeps the outcomes within these limits".to_string())
+        );
+        let limits = Limits::default()
+            .at_least(Outcome::Win, 2)
+            .at_most(Outcome::Win, 1);
+        assert!(optimize(&guide, &limits).is_err());
+    }
+
+    #[test]
+    fn test_optimize_matches_brute_force() {
+        let rules = Rules::load(concat!(env!("CARGO_MANIFEST_DIR"), "/rules/rpsls.toml")).unwrap();
+        let guide = Guide::parse("A V\nB V\nC V\nD V\nE V\nA V", rules).unwrap();
+        let shapes: Vec<Shape> = guide.rules.shapes().collect();
+        for wins in 0..=6 {
+            let limits = Limits::default()
+                .at_most(Outcome::Win, wins)
+                .at_least(Outcome::Loss, 1);
+            let mut best: Option<isize> = None;
+            for mut i in 0..shapes.len().pow(6) {
+                let mine: Vec<Shape> = (0..6)
+                    .map(|_| {
+                        let shape = shapes[i % shapes.len()];
+                        i /= shapes.len();
+                        shape
+                    })
+                    .collect();
+                let counts = outcomes(&guide, &mine);
+                if counts[Outcome::Win as usize] <= wins && counts[Outcome::Loss as usize] >= 1 {
+                    best = best.max(Some(total(&guide, &mine)));
+                }
+            }
+            let optimized = optimize(&guide, &limits).unwrap();
+            assert_eq!(Some(total(&guide, &optimized)), best, "{} wins", wins);
+        }
+    }
+}
@@ -0,0 +1,230 @@
+//! Where a guide's score comes from, round by round.
+
+use crate::reading::Reading;
+use crate::rules::Outcome;
+use crate::Guide;
+use std::fmt::Write;
+
+/// One round as played, with names as in the rules.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    /// Counting from 1, like the lines of the guide.
+    pub round: usize,
+    pub opponent: String,
+    pub me: String,
+    pub outcome: Outcome,
+    pub shape_points: isize,
+    pub outcome_points: isize,
+    /// The score after this round.
+    pub total: isize,
+}
+
+/// Every round of a guide played with one reading, and how many were won, drawn and lost.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Breakdown {
+    pub lines: Vec<Line>,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+}
+
+impl Breakdown {
+    pub fn new(guide: &Guide, reading: &Reading) -> Result<Breakdown, String> {
+        let rules = &guide.rules;
+        let mut breakdown = Breakdown {
+            lines: Vec::new(),
+            wins: 0,
+            draws: 0,
+            losses: 0,
+        };
+        let mut total = 0;
+        for (idx, round) in guide.rounds.iter().enumerate() {
+            let me = reading
+                .respond(rules, round.opponent, round.code)
+                .map_err(|e| format!("round {}: {}", idx + 1, e))?;
+            let outcome = rules.outcome(me, round.opponent);
+            match outcome {
+                Outcome::Win => breakdown.wins += 1,
+                Outcome::Draw => breakdown.draws += 1,
+                Outcome::Loss => breakdown.losses += 1,
+            }
+            let shape_points = rules.shape_score(me);
+            let outcome_points = rules.outcome_score(outcome);
+            total += shape_points + outcome_points;
+            breakdown.lines.push(Line {
+                round: idx + 1,
+                opponent: rules.name(round.opponent).to_string(),
+                me: rules.name(me).to_string(),
+                outcome,
+                shape_points,
+                outcome_points,
+                total,
+            });
+        }
+        Ok(breakdown)
+    }
+
+    pub fn total(&self) -> isize {
+        self.lines.last().map_or(0, |line| line.total)
+    }
+
+    /// An aligned table of the rounds followed by the counts of each outcome.
+    pub fn to_table(&self) -> String {
+        let width = self
+            .lines
+            .iter()
+            .flat_map(|line| [line.opponent.len(), line.me.len()])
+            .chain(["Opponent".len()])
+            .max()
+            .unwrap();
+        let mut out = format!(
+            "{:>5}  {:<w$}  {:<w$}  {:<7}  {:>5}  {:>7}  {:>7}\n",
+            "Round",
+            "Opponent",
+            "Me",
+            "Outcome",
+            "Shape",
+            "Outcome",
+            "Total",
+            w = width
+        );
+        for line in &self.lines {
+            writeln!(
+                out,
+                "{:>5}  {:<w$}  {:<w$}  {:<7}  {:>5}  {:>7}  {:>7}",
+                line.round,
+                line.opponent,
+                line.me,
+                line.outcome.name(),
+                line.shape_points,
+                line.outcome_points,
+                line.total,
+                w = width
+            )
+            .unwrap();
+        }
+        writeln!(
+            out,
+            "\nWins    {:>7}\nDraws   {:>7}\nLosses  {:>7}\nScore   {:>7}",
+            self.wins,
+            self.draws,
+            self.losses,
+            self.total()
+        )
+        .unwrap();
+        out
+    }
+
+    /// One row per round.
+    pub fn to_csv(&self) -> String {
+        let mut out = String::from("round,opponent,me,outcome,shape_points,outcome_points,total\n");
+        for line in &self.lines {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{}",
+                line.round,
+                field(&line.opponent),
+                field(&line.me),
+                line.outcome.name(),
+                line.shape_points,
+                line.outcome_points,
+                line.total
+            )
+            .unwrap();
+        }
+        out
+    }
+
+    /// The counts of each outcome and the score as a CSV of their own, in one row.
+    pub fn summary_csv(&self) -> String {
+        format!(
+            "wins,draws,losses,score\n{},{},{},{}\n",
+            self.wins,
+            self.draws,
+            self.losses,
+            self.total()
+        )
+    }
+}
+
+/// `text` as a CSV field, quoted if it has to be.
+fn field(text: &str) -> String {
+    if text.contains([',', '"', '\n']) {
+        format!("\"{}\"", text.replace('"', "\"\""))
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Rules;
+    use aoc_common::input;
+
+    fn sample() -> Guide {
+        Guide::parse(&input::read_input("sample").unwrap(), Rules::standard()).unwrap()
+    }
+
+    #[test]
+    fn test_breakdown() {
+        let guide = sample();
+        let breakdown = Breakdown::new(&guide, &Reading::Shapes).unwrap();
+        assert_eq!(
+            breakdown.lines[1],
+            Line {
+                round: 2,
+                opponent: "paper".to_string(),
+                me: "rock".to_string(),
+                outcome: Outcome::Loss,
+                shape_points: 1,
+                outcome_points: 0,
+                total: 9,
+            }
+        );
+        assert_eq!(
+            (breakdown.wins, breakdown.draws, breakdown.losses),
+            (1, 1, 1)
+        );
+        assert_eq!(breakdown.total(), 15);
+
+        let breakdown = Breakdown::new(&guide, &Reading::Outcomes).unwrap();
+        assert!(breakdown.lines.iter().all(|line| line.me == "rock"));
+        assert_eq!(
+            (breakdown.wins, breakdown.draws, breakdown.losses),
+            (1, 1, 1)
+        );
+        assert_eq!(breakdown.total(), 12);
+
+        let mapping = Reading::parse("X=rock", &guide.rules).unwrap();
+        assert_eq!(
+            Breakdown::new(&guide, &mapping),
+            Err("round 1: `Y` is not mapped".to_string())
+        );
+    }
+
+    #[test]
+    fn test_formats() {
+        let breakdown = Breakdown::new(&sample(), &Reading::Shapes).unwrap();
+        let table = breakdown.to_table();
+        assert!(table.starts_with(
+            "Round  Opponent  Me        Outcome  Shape  Outcome    Total\n\
+             \x20   1  rock      paper     win          2        6        8\n"
+        ));
+        assert!(table.ends_with("Losses        1\nScore        15\n"));
+
+        let csv = breakdown.to_csv();
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("round,opponent,me,outcome,shape_points,outcome_points,total")
+        );
+        assert_eq!(lines.next(), Some("1,rock,paper,win,2,6,8"));
+        assert_eq!(lines.last(), Some("3,scissors,scissors,draw,3,3,15"));
+        assert_eq!(
+            breakdown.summary_csv(),
+            "wins,draws,losses,score\n1,1,1,15\n"
+        );
+        assert_eq!(field("rock, paper"), "\"rock, paper\"");
+    }
+}
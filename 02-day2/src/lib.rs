@@ -0,0 +1,181 @@
+use aoc_common::{Answer, ParseError, Solution};
+use reading::Reading;
+use rules::{Rules, Shape};
+
+pub mod analysis;
+pub mod breakdown;
+pub mod reading;
+pub mod rules;
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    type Parsed = Guide;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Guide::parse(input, Rules::standard())
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed)
+            .expect("the standard rules know every letter")
+            .into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed)
+            .expect("the standard rules know every letter")
+            .into()
+    }
+}
+
+/// The encrypted strategy guide together with the rules it is played by.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Guide {
+    pub rules: Rules,
+    pub rounds: Vec<Round>,
+}
+
+/// One line of the guide: what the opponent plays and the letter in our column, whose
+/// meaning depends on the part.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Round {
+    pub opponent: Shape,
+    pub code: char,
+}
+
+impl Guide {
+    /// Reads one round per line, the opponent's letter and ours separated by a space. Both
+    /// have to be letters of `rules`.
+    pub fn parse(input: &str, rules: Rules) -> Result<Guide, ParseError> {
+        let opponent_codes = rules.opponent_codes();
+        let response_codes = rules.response_codes();
+        let mut rounds: Vec<Round> = Vec::new();
+        for (idx, line) in input.lines().enumerate() {
+            let mut it = line.splitn(2, ' ');
+            let opp = parse_column(idx + 1, line, it.next(), &opponent_codes)?;
+            let code = parse_column(idx + 1, line, it.next(), &response_codes)?;
+            rounds.push(Round {
+                opponent: rules.opponent_shape(opp).unwrap(),
+                code,
+            });
+        }
+        Ok(Guide { rules, rounds })
+    }
+
+    /// Our total score following the guide with our column read as `reading`.
+    pub fn score(&self, reading: &Reading) -> Result<isize, String> {
+        self.rounds
+            .iter()
+            .map(|round| {
+                let me = reading.respond(&self.rules, round.opponent, round.code)?;
+                Ok(self.rules.score(me, round.opponent))
+            })
+            .sum()
+    }
+}
+
+fn parse_column(
+    line_no: usize,
+    line: &str,
+    token: Option<&str>,
+    allowed: &[char],
+) -> Result<char, ParseError> {
+    let expected = one_of(allowed);
+    match token {
+        Some(t) if t.chars().count() == 1 && allowed.iter().any(|c| t.starts_with(*c)) => {
+            Ok(t.chars().next().unwrap())
+        }
+        Some(t) => Err(ParseError::at(line_no, line, t, &expected)),
+        None => Err(ParseError::new(
+            line_no,
+            line.chars().count() + 1,
+            "",
+            &expected,
+        )),
+    }
+}
+
+/// "`A`, `B` or `C`"
+fn one_of(allowed: &[char]) -> String {
+    let quoted: Vec<String> = allowed.iter().map(|c| format!("`{}`", c)).collect();
+    match quoted.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+        _ => quoted.concat(),
+    }
+}
+
+/// Our total score if our column says which shape to play.
+fn part1(guide: &Guide) -> Result<isize, String> {
+    guide.score(&Reading::Shapes)
+}
+
+/// Our total score if our column says how the round has to end.
+fn part2(guide: &Guide) -> Result<isize, String> {
+    guide.score(&Reading::Outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::input;
+
+    fn sample() -> Guide {
+        Guide::parse(&input::read_input("sample").unwrap(), Rules::standard()).unwrap()
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&sample()), Ok(15));
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&sample()), Ok(12));
+    }
+
+    #[test]
+    fn test_rpsls() {
+        let rules = Rules::load(concat!(env!("CARGO_MANIFEST_DIR"), "/rules/rpsls.toml")).unwrap();
+        let guide = Guide::parse("A Y\nD Z\nE X", rules.clone()).unwrap();
+        // spock vaporizes rock, lizard poisons spock, scissors decapitate lizard
+        assert_eq!(part1(&guide), Ok(10 + 11 + 9));
+        // rock draws, paper disproves spock, paper loses to lizard
+        assert_eq!(part2(&guide), Ok(4 + 8 + 2));
+
+        let guide = Guide::parse("A V", rules).unwrap();
+        assert_eq!(
+            part2(&guide),
+            Err("`V` is not an outcome in these rules".to_string())
+        );
+    }
+
+    #[test]
+    fn test_mapping() {
+        let guide = sample();
+        let reading = |text: &str| Reading::parse(text, &guide.rules).unwrap();
+        assert_eq!(guide.score(&reading("X=rock,Y=paper,Z=scissors")), Ok(15));
+        assert_eq!(guide.score(&reading("X=loss,Y=draw,Z=win")), Ok(12));
+        // always win: paper, scissors, rock
+        assert_eq!(guide.score(&reading("X=win,Y=win,Z=win")), Ok(8 + 9 + 7));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let parse = |input: &str| Guide::parse(input, Rules::standard()).err();
+        assert_eq!(
+            parse("A Y\nD X"),
+            Some(ParseError::new(2, 1, "D", "`A`, `B` or `C`"))
+        );
+        assert_eq!(
+            parse("A XY"),
+            Some(ParseError::new(1, 3, "XY", "`X`, `Y` or `Z`"))
+        );
+        assert_eq!(
+            parse("A"),
+            Some(ParseError::new(1, 2, "", "`X`, `Y` or `Z`"))
+        );
+        assert_eq!(one_of(&['A']), "`A`");
+    }
+}
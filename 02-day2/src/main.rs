@@ -1,134 +1,210 @@
-use std::fs;
+use aoc_common::input::InputSource;
+use aoc_common::solution;
+use day02::analysis::{self, Analysis, Limits};
+use day02::breakdown::Breakdown;
+use day02::reading::Reading;
+use day02::rules::{Outcome, Rules};
+use day02::{Day02, Guide};
+use std::env;
+use std::process::ExitCode;
 
-fn main() {
-    let input: Vec<String> = fs::read_to_string("input")
-        .unwrap()
-        .lines()
-        .map(|l| l.to_string())
-        .collect();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-}
+const DEFAULT_INPUT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/input");
+const USAGE: &str = "usage: day02 [--rules FILE] [--reading shape|outcome|X=rock,Y=win,…] [input|-]
+       day02 [--rules FILE] --breakdown[=text|csv|summary] [--reading …] [input|-]
+       day02 [--rules FILE] --analyze|--optimize [--at-most OUTCOME=N] [--at-least OUTCOME=N] [input|-]";
+/// The flags of this day, some taking a value after `=`; without any of them the binary takes
+/// the shared flags of [`solution::main`], `--format` among them.
+const OPTIONS: [&str; 7] = [
+    "--rules",
+    "--reading",
+    "--breakdown",
+    "--analyze",
+    "--optimize",
+    "--at-most",
+    "--at-least",
+];
 
-fn part1(input: &Vec<String>) -> isize {
-    let mut overall_score: isize = 0;
-    for line in input {
-        let mut it = line.splitn(2, ' ');
-        let opp = it.next().unwrap().parse::<char>().unwrap();
-        let me = it.next().unwrap().parse::<char>().unwrap();
-        let mut rps = RPS::new(opp, me);
-        rps.play();
-        overall_score += rps.score;
-    }
+/// What the binary does with the guide.
+enum Mode {
+    /// Scores it with our column read one way, or both puzzle ways.
+    Play(Option<String>),
+    /// Every round with our column read one way, the first puzzle way by default.
+    Breakdown(Option<String>, Format),
+    /// The best, worst and expected scores of its opponent column, and the best within limits.
+    Analyze(Limits),
+    /// The best guide within limits for its opponent column.
+    Optimize(Limits),
+}
 
-    return overall_score;
+/// How `--breakdown` prints the rounds.
+enum Format {
+    Text,
+    Csv,
+    /// Only the counts of each outcome and the score, as CSV.
+    Summary,
 }
 
-struct RPS {
-    opp: char,
-    me: char,
-    score: isize,
+/// The game to play the guide by, when not the puzzle's, and what to do with it.
+struct Options {
+    rules: Option<String>,
+    mode: Mode,
+    input: Option<String>,
 }
 
-impl RPS {
-    fn new(opp: char, me: char) -> RPS {
-        RPS {
-            opp: opp,
-            me: me,
-            score: 0,
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.iter().any(|a| OPTIONS.contains(&flag(a))) {
+        return solution::main(&Day02, DEFAULT_INPUT);
+    }
+    let options = match parse_args(&args) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            eprintln!("{}\n{}", e, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let source = InputSource::resolve(options.input.as_deref(), DEFAULT_INPUT);
+    match run(&source, &options) {
+        Ok(out) => {
+            print!("{}", out);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
         }
     }
+}
 
-    fn play(&mut self) {
-        match self.me {
-            'X' => {
-                self.score += 1;
-                match self.opp {
-                    'A' => self.score += 3,
-                    'B' => self.score += 0,
-                    'C' => self.score += 6,
-                    _ => panic!("Unexpected choice for me: {}", self.me),
-                }
-            }
-            'Y' => {
-                self.score += 2;
-                match self.opp {
-                    'A' => self.score += 6,
-                    'B' => self.score += 3,
-                    'C' => self.score += 0,
-                    _ => panic!("Unexpected choice for me: {}", self.me),
-                }
+/// `--breakdown` of `--breakdown=csv`.
+fn flag(arg: &str) -> &str {
+    arg.split_once('=').map_or(arg, |(flag, _)| flag)
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut rules: Option<String> = None;
+    let mut reading: Option<String> = None;
+    let mut breakdown: Option<Format> = None;
+    let mut analyze = false;
+    let mut optimize = false;
+    let mut limits: Option<Limits> = None;
+    let mut input: Option<String> = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--rules" => rules = Some(args.next().ok_or("--rules needs a file")?.clone()),
+            "--reading" => reading = Some(args.next().ok_or("--reading needs a reading")?.clone()),
+            a if flag(a) == "--breakdown" => {
+                // the format is part of the flag so that it can't be taken for the input
+                breakdown = Some(match a.split_once('=').map(|(_, f)| f) {
+                    None | Some("text") => Format::Text,
+                    Some("csv") => Format::Csv,
+                    Some("summary") => Format::Summary,
+                    Some(f) => return Err(format!("unknown breakdown format `{}`", f)),
+                });
             }
-            'Z' => {
-                self.score += 3;
-                match self.opp {
-                    'A' => self.score += 0,
-                    'B' => self.score += 6,
-                    'C' => self.score += 3,
-                    _ => panic!("Unexpected choice for me: {}", self.me),
-                }
+            "--analyze" => analyze = true,
+            "--optimize" => optimize = true,
+            "--at-most" | "--at-least" => {
+                let (outcome, rounds) = parse_limit(args.next().map(|l| l.as_str()))?;
+                let base = limits.take().unwrap_or_default();
+                limits = Some(match arg.as_str() {
+                    "--at-most" => base.at_most(outcome, rounds),
+                    _ => base.at_least(outcome, rounds),
+                });
             }
-            _ => panic!("Unexpected choice for opp: {}", self.opp),
+            a if a.starts_with('-') && a != "-" => return Err(format!("unknown option `{}`", a)),
+            _ if input.is_none() => input = Some(arg.clone()),
+            _ => return Err(format!("unexpected argument `{}`", arg)),
         }
     }
-}
-
-fn part2(input: &Vec<String>) -> isize {
-    let mut overall_score: isize = 0;
-    for line in input {
-        let mut it = line.splitn(2, ' ');
-        let opp = it.next().unwrap().parse::<char>().unwrap();
-        let outc = it.next().unwrap().parse::<char>().unwrap();
-        let mut rps = RPS2::new(opp, outc);
-        rps.play();
-        overall_score += rps.score;
+    if breakdown.is_some() && (analyze || optimize) {
+        return Err("--breakdown can't be combined with --analyze or --optimize".to_string());
     }
-
-    return overall_score;
+    let mode = match (reading, analyze, optimize) {
+        (_, true, true) => return Err("--analyze and --optimize can't be combined".to_string()),
+        (Some(_), true, _) | (Some(_), _, true) => {
+            return Err("--reading doesn't apply to --analyze or --optimize".to_string())
+        }
+        (reading, false, false) if limits.is_none() => match breakdown {
+            Some(format) => Mode::Breakdown(reading, format),
+            None => Mode::Play(reading),
+        },
+        (_, false, false) => {
+            return Err(
+                "--at-most and --at-least only apply to --analyze or --optimize".to_string(),
+            )
+        }
+        (None, true, false) => Mode::Analyze(limits.unwrap_or_default()),
+        (None, false, true) => Mode::Optimize(limits.unwrap_or_default()),
+    };
+    Ok(Options { rules, mode, input })
 }
 
-struct RPS2 {
-    opp: char,
-    outc: char,
-    score: isize,
+/// `win=3` as the outcome and the number of rounds.
+fn parse_limit(limit: Option<&str>) -> Result<(Outcome, usize), String> {
+    let (outcome, rounds) = limit
+        .and_then(|l| l.split_once('='))
+        .ok_or("a limit looks like `win=3`")?;
+    let rounds = rounds
+        .parse()
+        .map_err(|_| format!("`{}` is not a number of rounds", rounds))?;
+    Ok((outcome.parse()?, rounds))
 }
 
-impl RPS2 {
-    fn new(opp: char, outc: char) -> RPS2 {
-        RPS2 {
-            opp: opp,
-            outc: outc,
-            score: 0,
+fn run(source: &InputSource, options: &Options) -> Result<String, String> {
+    let rules = match &options.rules {
+        Some(path) => Rules::load(path)?,
+        None => Rules::standard(),
+    };
+    let input = source
+        .read()
+        .map_err(|e| format!("Could not read {}: {}", source, e))?;
+    let guide =
+        Guide::parse(&input, rules).map_err(|e| format!("Could not parse {}: {}", source, e))?;
+    match &options.mode {
+        Mode::Play(reading) => play(&guide, reading.as_deref()),
+        Mode::Breakdown(reading, format) => {
+            let reading = match reading {
+                Some(text) => Reading::parse(text, &guide.rules)?,
+                None => Reading::Shapes,
+            };
+            let breakdown = Breakdown::new(&guide, &reading)?;
+            Ok(match format {
+                Format::Text => breakdown.to_table(),
+                Format::Csv => breakdown.to_csv(),
+                Format::Summary => breakdown.summary_csv(),
+            })
         }
+        Mode::Analyze(limits) => analyze(&guide, limits),
+        Mode::Optimize(limits) => Ok(analysis::to_guide(
+            &guide,
+            &analysis::optimize(&guide, limits)?,
+        )),
     }
+}
 
-    fn play(&mut self) {
-        match self.outc {
-            'X' => match self.opp {
-                'A' => self.score += 3,
-                'B' => self.score += 1,
-                'C' => self.score += 2,
-                _ => panic!("Unexpected choice for opp: {}", self.opp),
-            },
-            'Y' => {
-                self.score += 3;
-                match self.opp {
-                    'A' => self.score += 1,
-                    'B' => self.score += 2,
-                    'C' => self.score += 3,
-                    _ => panic!("Unexpected choice for opp: {}", self.opp),
-                }
-            }
-            'Z' => {
-                self.score += 6;
-                match self.opp {
-                    'A' => self.score += 2,
-                    'B' => self.score += 3,
-                    'C' => self.score += 1,
-                    _ => panic!("Unexpected choice for opp: {}", self.opp),
-                }
-            }
-            _ => panic!("Unexpected choice for outc: {}", self.outc),
-        }
+/// With a reading, the score of that reading, otherwise both parts. A part the rules have no
+/// meaning for, like outcomes when our column only has shape letters, reports why instead.
+fn play(guide: &Guide, reading: Option<&str>) -> Result<String, String> {
+    if let Some(reading) = reading {
+        let reading = Reading::parse(reading, &guide.rules)?;
+        return Ok(format!("Score: {}\n", guide.score(&reading)?));
     }
+    let answer = |score: Result<isize, String>| score.map_or_else(|e| e, |s| s.to_string());
+    Ok(format!(
+        "Part 1: {}\nPart 2: {}\n",
+        answer(guide.score(&Reading::Shapes)),
+        answer(guide.score(&Reading::Outcomes))
+    ))
+}
+
+fn analyze(guide: &Guide, limits: &Limits) -> Result<String, String> {
+    let analysis = Analysis::new(guide);
+    let shapes = analysis::optimize(guide, limits)?;
+    let optimized = analysis::total(guide, &shapes);
+    Ok(format!(
+        "Best       {:>10}\nWorst      {:>10}\nExpected   {:>12.1}\nOptimized  {:>10}\n",
+        analysis.best, analysis.worst, analysis.expected, optimized
+    ))
 }
@@ -0,0 +1,132 @@
+//! How to read the second column of a strategy guide. The puzzle reads it as our shape in
+//! part 1 and as the outcome we're after in part 2; a [`Reading::Mapping`] gives every letter
+//! a meaning of its own, to try other readings of the same guide.
+
+use crate::rules::{Outcome, Rules, Shape};
+
+/// What a letter of our column asks for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Play {
+    Shape(Shape),
+    Outcome(Outcome),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reading {
+    /// Letters are shapes, by the response letters of the rules.
+    Shapes,
+    /// Letters are outcomes, by the outcome letters of the rules.
+    Outcomes,
+    /// Every letter is a shape or an outcome as listed.
+    Mapping(Vec<(char, Play)>),
+}
+
+impl Reading {
+    /// Reads `shape`, `outcome` or a mapping like `X=rock,Y=draw,Z=win`, whose shapes are
+    /// named as in `rules`.
+    pub fn parse(text: &str, rules: &Rules) -> Result<Reading, String> {
+        match text {
+            "shape" => return Ok(Reading::Shapes),
+            "outcome" => return Ok(Reading::Outcomes),
+            _ => {}
+        }
+        let mut mapping: Vec<(char, Play)> = Vec::new();
+        for entry in text.split(',') {
+            let (code, meaning) = entry.split_once('=').ok_or(format!(
+                "expected `shape`, `outcome` or `LETTER=meaning,…`, found `{}`",
+                entry
+            ))?;
+            let mut chars = code.trim().chars();
+            let (Some(code), None) = (chars.next(), chars.next()) else {
+                return Err(format!("`{}` is not a single letter", code.trim()));
+            };
+            if mapping.iter().any(|(c, _)| *c == code) {
+                return Err(format!("`{}` is mapped twice", code));
+            }
+            let meaning = meaning.trim();
+            let play = match rules.shape_named(meaning) {
+                Some(shape) => Play::Shape(shape),
+                None => Play::Outcome(
+                    meaning
+                        .parse()
+                        .map_err(|_| format!("`{}` is neither a shape nor an outcome", meaning))?,
+                ),
+            };
+            mapping.push((code, play));
+        }
+        Ok(Reading::Mapping(mapping))
+    }
+
+    /// What `code` asks for in this reading.
+    pub fn play(&self, rules: &Rules, code: char) -> Result<Play, String> {
+        match self {
+            Reading::Shapes => rules
+                .response_shape(code)
+                .map(Play::Shape)
+                .ok_or(format!("`{}` is not a shape in these rules", code)),
+            Reading::Outcomes => rules
+                .outcome_of(code)
+                .map(Play::Outcome)
+                .ok_or(format!("`{}` is not an outcome in these rules", code)),
+            Reading::Mapping(mapping) => mapping
+                .iter()
+                .find(|(c, _)| *c == code)
+                .map(|(_, play)| *play)
+                .ok_or(format!("`{}` is not mapped", code)),
+        }
+    }
+
+    /// The shape we play against `opponent` when our column says `code`.
+    pub fn respond(&self, rules: &Rules, opponent: Shape, code: char) -> Result<Shape, String> {
+        match self.play(rules, code)? {
+            Play::Shape(shape) => Ok(shape),
+            Play::Outcome(outcome) => rules.respond(opponent, outcome).ok_or(format!(
+                "no shape ends in a {} against {}",
+                outcome.name(),
+                rules.name(opponent)
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let rules = Rules::standard();
+        assert_eq!(Reading::parse("shape", &rules), Ok(Reading::Shapes));
+        assert_eq!(Reading::parse("outcome", &rules), Ok(Reading::Outcomes));
+        assert_eq!(
+            Reading::parse("X=rock, Y=win", &rules),
+            Ok(Reading::Mapping(vec![
+                ('X', Play::Shape(Shape(0))),
+                ('Y', Play::Outcome(Outcome::Win))
+            ]))
+        );
+        let error = |text: &str| Reading::parse(text, &rules).unwrap_err();
+        assert_eq!(
+            error("shapes"),
+            "expected `shape`, `outcome` or `LETTER=meaning,…`, found `shapes`"
+        );
+        assert_eq!(error("XY=rock"), "`XY` is not a single letter");
+        assert_eq!(error("X=rock,X=paper"), "`X` is mapped twice");
+        assert_eq!(error("X=well"), "`well` is neither a shape nor an outcome");
+    }
+
+    #[test]
+    fn test_respond() {
+        let rules = Rules::standard();
+        let [rock, paper, scissors] = [Shape(0), Shape(1), Shape(2)];
+        assert_eq!(Reading::Shapes.respond(&rules, rock, 'Z'), Ok(scissors));
+        assert_eq!(Reading::Outcomes.respond(&rules, rock, 'Z'), Ok(paper));
+        let mapping = Reading::parse("X=paper,Y=loss", &rules).unwrap();
+        assert_eq!(mapping.respond(&rules, scissors, 'X'), Ok(paper));
+        assert_eq!(mapping.respond(&rules, scissors, 'Y'), Ok(paper));
+        assert_eq!(
+            mapping.respond(&rules, rock, 'Z'),
+            Err("`Z` is not mapped".to_string())
+        );
+    }
+}
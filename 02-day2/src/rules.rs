@@ -0,0 +1,357 @@
+//! The rules of a rock paper scissors style game as data: the shapes, which shape beats which,
+//! what everything scores and the letters the strategy guide uses for them. The puzzle's game
+//! is [`Rules::standard`]; others, like rock paper scissors lizard Spock, are loaded from TOML
+//! files in the same format as `rules/rps.toml`.
+
+use serde::Deserialize;
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+/// A shape of the game, by its position in the rules it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Shape(pub usize);
+
+/// How a round ends for us.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Loss, Outcome::Draw, Outcome::Win];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Outcome::Loss => "loss",
+            Outcome::Draw => "draw",
+            Outcome::Win => "win",
+        }
+    }
+}
+
+impl FromStr for Outcome {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Outcome, String> {
+        Outcome::ALL
+            .into_iter()
+            .find(|o| o.name() == s)
+            .ok_or(format!(
+                "unknown outcome `{}`, expected `loss`, `draw` or `win`",
+                s
+            ))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ShapeRule {
+    name: String,
+    score: isize,
+    /// The letter for the shape in the opponent's column.
+    opponent: char,
+    /// The letter for the shape in our column, when it names a shape.
+    response: char,
+}
+
+/// A game: its shapes, which shapes each one beats, and the scores and letters of shapes and
+/// outcomes. Shapes that don't beat each other draw.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rules {
+    shapes: Vec<ShapeRule>,
+    /// `beats[a][b]` is whether shape `a` beats shape `b`.
+    beats: Vec<Vec<bool>>,
+    /// Scores and letters of the outcomes in the order of [`Outcome::ALL`].
+    outcome_scores: [isize; 3],
+    outcome_codes: [char; 3],
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RulesFile {
+    outcomes: OutcomesFile,
+    shapes: Vec<ShapeFile>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OutcomesFile {
+    loss: OutcomeFile,
+    draw: OutcomeFile,
+    win: OutcomeFile,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct OutcomeFile {
+    score: isize,
+    code: char,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct ShapeFile {
+    name: String,
+    score: isize,
+    opponent: char,
+    response: char,
+    beats: Vec<String>,
+}
+
+impl Rules {
+    /// Rock paper scissors with the puzzle's letters and scores.
+    pub fn standard() -> Rules {
+        Rules::parse(include_str!("../rules/rps.toml")).expect("the standard rules are valid")
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Rules, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        Rules::parse(&text).map_err(|e| format!("Could not parse {}: {}", path.display(), e))
+    }
+
+    /// Reads rules from TOML and checks that they make a game: names and letters are unique,
+    /// every beaten shape exists, and no shape beats itself or a shape that beats it.
+    pub fn parse(text: &str) -> Result<Rules, String> {
+        let file: RulesFile = toml::from_str(text).map_err(|e| e.to_string())?;
+        if file.shapes.is_empty() {
+            return Err("a game needs at least one shape".to_string());
+        }
+        let shapes: Vec<ShapeRule> = file
+            .shapes
+            .iter()
+            .map(|s| ShapeRule {
+                name: s.name.clone(),
+                score: s.score,
+                opponent: s.opponent,
+                response: s.response,
+            })
+            .collect();
+        unique("shape name", shapes.iter().map(|s| s.name.as_str()))?;
+        unique("opponent letter", shapes.iter().map(|s| s.opponent))?;
+        unique("response letter", shapes.iter().map(|s| s.response))?;
+        let outcomes = [file.outcomes.loss, file.outcomes.draw, file.outcomes.win];
+        unique("outcome letter", outcomes.iter().map(|o| o.code))?;
+
+        let mut beats: Vec<Vec<bool>> = vec![vec![false; shapes.len()]; shapes.len()];
+        for (a, shape) in file.shapes.iter().enumerate() {
+            for name in &shape.beats {
+                let Some(b) = shapes.iter().position(|s| s.name == *name) else {
+                    return Err(format!(
+                        "`{}` beats an unknown shape `{}`",
+                        shape.name, name
+                    ));
+                };
+                if a == b {
+                    return Err(format!("`{}` can't beat itself", name));
+                }
+                beats[a][b] = true;
+            }
+        }
+        for a in 0..shapes.len() {
+            for b in 0..a {
+                if beats[a][b] && beats[b][a] {
+                    return Err(format!(
+                        "`{}` and `{}` can't beat each other",
+                        shapes[a].name, shapes[b].name
+                    ));
+                }
+            }
+        }
+        Ok(Rules {
+            shapes,
+            beats,
+            outcome_scores: outcomes.each_ref().map(|o| o.score),
+            outcome_codes: outcomes.each_ref().map(|o| o.code),
+        })
+    }
+
+    /// Every shape of the game, in the order of the rules.
+    pub fn shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.shapes.len()).map(Shape)
+    }
+
+    pub fn name(&self, shape: Shape) -> &str {
+        &self.shapes[shape.0].name
+    }
+
+    pub fn shape_named(&self, name: &str) -> Option<Shape> {
+        self.shapes.iter().position(|s| s.name == name).map(Shape)
+    }
+
+    /// How a round ends for us playing `me` against `opponent`.
+    pub fn outcome(&self, me: Shape, opponent: Shape) -> Outcome {
+        if self.beats[me.0][opponent.0] {
+            Outcome::Win
+        } else if self.beats[opponent.0][me.0] {
+            Outcome::Loss
+        } else {
+            Outcome::Draw
+        }
+    }
+
+    /// Our score for a round: the score of our shape plus that of the outcome.
+    pub fn score(&self, me: Shape, opponent: Shape) -> isize {
+        self.shape_score(me) + self.outcome_score(self.outcome(me, opponent))
+    }
+
+    pub fn shape_score(&self, shape: Shape) -> isize {
+        self.shapes[shape.0].score
+    }
+
+    pub fn outcome_score(&self, outcome: Outcome) -> isize {
+        self.outcome_scores[outcome.index()]
+    }
+
+    /// The first shape that ends a round against `opponent` with `outcome`, if any does.
+    pub fn respond(&self, opponent: Shape, outcome: Outcome) -> Option<Shape> {
+        self.shapes()
+            .find(|&me| self.outcome(me, opponent) == outcome)
+    }
+
+    /// The shape the opponent plays with `code`.
+    pub fn opponent_shape(&self, code: char) -> Option<Shape> {
+        self.shapes
+            .iter()
+            .position(|s| s.opponent == code)
+            .map(Shape)
+    }
+
+    /// The shape `code` stands for in our column.
+    pub fn response_shape(&self, code: char) -> Option<Shape> {
+        self.shapes
+            .iter()
+            .position(|s| s.response == code)
+            .map(Shape)
+    }
+
+    /// The outcome `code` stands for in our column.
+    pub fn outcome_of(&self, code: char) -> Option<Outcome> {
+        let i = self.outcome_codes.iter().position(|&c| c == code)?;
+        Some(Outcome::ALL[i])
+    }
+
+    /// The letter the opponent plays `shape` with.
+    pub fn opponent_code(&self, shape: Shape) -> char {
+        self.shapes[shape.0].opponent
+    }
+
+    /// The letter for `shape` in our column.
+    pub fn response_code(&self, shape: Shape) -> char {
+        self.shapes[shape.0].response
+    }
+
+    /// The letters of the opponent's column.
+    pub fn opponent_codes(&self) -> Vec<char> {
+        self.shapes.iter().map(|s| s.opponent).collect()
+    }
+
+    /// The letters of our column: those of the shapes, then those of the outcomes that aren't
+    /// a shape as well.
+    pub fn response_codes(&self) -> Vec<char> {
+        let mut codes: Vec<char> = self.shapes.iter().map(|s| s.response).collect();
+        for code in self.outcome_codes {
+            if !codes.contains(&code) {
+                codes.push(code);
+            }
+        }
+        codes
+    }
+}
+
+fn unique<T: PartialEq + std::fmt::Display>(
+    what: &str,
+    values: impl Iterator<Item = T>,
+) -> Result<(), String> {
+    let mut seen: Vec<T> = Vec::new();
+    for value in values {
+        if seen.contains(&value) {
+            return Err(format!("the {} `{}` is used twice", what, value));
+        }
+        seen.push(value);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rpsls() -> Rules {
+        Rules::load(concat!(env!("CARGO_MANIFEST_DIR"), "/rules/rpsls.toml")).unwrap()
+    }
+
+    #[test]
+    fn test_standard() {
+        let rules = Rules::standard();
+        let [rock, paper, scissors] = [Shape(0), Shape(1), Shape(2)];
+        assert_eq!(rules.name(scissors), "scissors");
+        assert_eq!(rules.outcome(paper, rock), Outcome::Win);
+        assert_eq!(rules.outcome(rock, paper), Outcome::Loss);
+        assert_eq!(rules.outcome(scissors, scissors), Outcome::Draw);
+        assert_eq!(rules.score(paper, rock), 8);
+        assert_eq!(rules.score(rock, paper), 1);
+        assert_eq!(rules.score(scissors, scissors), 6);
+        assert_eq!(rules.respond(rock, Outcome::Loss), Some(scissors));
+        assert_eq!(rules.opponent_shape('B'), Some(paper));
+        assert_eq!(rules.response_shape('Z'), Some(scissors));
+        assert_eq!(rules.opponent_code(paper), 'B');
+        assert_eq!(rules.response_code(scissors), 'Z');
+        assert_eq!(rules.outcome_of('Y'), Some(Outcome::Draw));
+        assert_eq!(rules.shape_named("paper"), Some(paper));
+        assert_eq!("win".parse(), Ok(Outcome::Win));
+        assert!("tie".parse::<Outcome>().is_err());
+        assert_eq!(rules.opponent_codes(), vec!['A', 'B', 'C']);
+        assert_eq!(rules.response_codes(), vec!['X', 'Y', 'Z']);
+    }
+
+    #[test]
+    fn test_rpsls_is_balanced() {
+        let rules = rpsls();
+        assert_eq!(rules.shapes().count(), 5);
+        for opponent in rules.shapes() {
+            for outcome in [Outcome::Win, Outcome::Loss] {
+                let count = rules
+                    .shapes()
+                    .filter(|&me| rules.outcome(me, opponent) == outcome)
+                    .count();
+                assert_eq!(count, 2, "{} {:?}", rules.name(opponent), outcome);
+            }
+        }
+        let spock = rules.opponent_shape('D').unwrap();
+        let lizard = rules.response_shape('Z').unwrap();
+        assert_eq!(rules.outcome(lizard, spock), Outcome::Win);
+        assert_eq!(rules.response_codes(), vec!['V', 'W', 'X', 'Y', 'Z']);
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let standard = include_str!("../rules/rps.toml");
+        let error =
+            |from: &str, to: &str| Rules::parse(&standard.replacen(from, to, 1)).unwrap_err();
+        assert_eq!(
+            error("beats = [\"scissors\"]", "beats = [\"well\"]"),
+            "`rock` beats an unknown shape `well`"
+        );
+        assert_eq!(
+            error("beats = [\"scissors\"]", "beats = [\"rock\"]"),
+            "`rock` can't beat itself"
+        );
+        assert_eq!(
+            error("beats = [\"scissors\"]", "beats = [\"paper\"]"),
+            "`paper` and `rock` can't beat each other"
+        );
+        assert_eq!(
+            error("opponent = \"B\"", "opponent = \"A\""),
+            "the opponent letter `A` is used twice"
+        );
+        assert!(Rules::parse("[outcomes]").is_err());
+    }
+}
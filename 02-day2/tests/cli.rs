@@ -0,0 +1,61 @@
+//! The binary's own flags next to the shared ones of `solution::main`.
+
+use std::process::{Command, Output};
+
+fn day02(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_day02"))
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn test_shared_format() {
+    let out = stdout(&day02(&["--format", "json", "sample"]));
+    let answers: Vec<serde_json::Value> = out
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert_eq!(answers.len(), 2);
+    assert_eq!(answers[0]["answer"], 15);
+    assert_eq!(answers[1]["answer"], 12);
+}
+
+#[test]
+fn test_breakdown_formats() {
+    let csv = stdout(&day02(&["--breakdown=csv", "sample"]));
+    assert!(csv.starts_with("round,opponent,me,outcome,"));
+    assert_eq!(csv.lines().count(), 4);
+    let summary = stdout(&day02(&["--breakdown=summary", "sample"]));
+    assert_eq!(summary, "wins,draws,losses,score\n1,1,1,15\n");
+    let table = stdout(&day02(&["--breakdown", "sample"]));
+    assert!(table.starts_with("Round  Opponent"));
+    let unknown = day02(&["--breakdown=xml", "sample"]);
+    assert!(!unknown.status.success());
+}
+
+#[test]
+fn test_breakdown_of_a_file_named_like_a_format() {
+    let dir = std::env::temp_dir().join(format!("day02-cli-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("csv"), "A Y\n").unwrap();
+    let output = Command::new(env!("CARGO_BIN_EXE_day02"))
+        .args(["--breakdown", "csv"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    std::fs::remove_dir_all(&dir).unwrap();
+    let table = stdout(&output);
+    assert!(table.starts_with("Round  Opponent"));
+    assert!(table.ends_with("Score         8\n"));
+}
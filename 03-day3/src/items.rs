@@ -0,0 +1,163 @@
+//! Items are the letters `a`-`z` and `A`-`Z`. A set of them fits into the low 52 bits of a
+//! `u64`, one bit per item in priority order, so comparing rucksacks is a single `&`.
+
+use std::ops::{BitAnd, BitOr};
+
+/// The priority of `item`: 1 through 26 for `a` through `z`, 27 through 52 for `A` through
+/// `Z`. Anything else isn't an item and has none.
+pub fn priority(item: char) -> Option<u8> {
+    match item {
+        'a'..='z' => Some(item as u8 - b'a' + 1),
+        'A'..='Z' => Some(item as u8 - b'A' + 27),
+        _ => None,
+    }
+}
+
+/// The item with `priority`, which has to be 1 through 52.
+fn item(priority: u8) -> char {
+    match priority {
+        1..=26 => (b'a' + priority - 1) as char,
+        27..=52 => (b'A' + priority - 27) as char,
+        _ => panic!("no item has priority {}", priority),
+    }
+}
+
+/// A set of items, bit `p - 1` standing for the item with priority `p`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    pub const EMPTY: ItemSet = ItemSet(0);
+    pub const ALL: ItemSet = ItemSet((1 << 52) - 1);
+
+    /// The items of `items`, or the byte offset and character of the first that isn't one.
+    pub fn from_items(items: &str) -> Result<ItemSet, (usize, char)> {
+        let mut set = ItemSet::EMPTY;
+        for (offset, ch) in items.char_indices() {
+            if priority(ch).is_none() {
+                return Err((offset, ch));
+            }
+            set.insert(ch);
+        }
+        Ok(set)
+    }
+
+    /// Adds `item` and returns whether it is a new item of the set.
+    pub fn insert(&mut self, item: char) -> bool {
+        let Some(p) = priority(item) else {
+            return false;
+        };
+        let had = self.contains(item);
+        self.0 |= 1 << (p - 1);
+        !had
+    }
+
+    pub fn contains(self, item: char) -> bool {
+        priority(item).is_some_and(|p| self.0 & (1 << (p - 1)) != 0)
+    }
+
+    pub fn intersection(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 & other.0)
+    }
+
+    pub fn union(self, other: ItemSet) -> ItemSet {
+        ItemSet(self.0 | other.0)
+    }
+
+    pub fn len(self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The items in priority order.
+    pub fn items(self) -> impl Iterator<Item = char> {
+        (1..=52u8)
+            .filter(move |p| self.0 & (1 << (p - 1)) != 0)
+            .map(item)
+    }
+
+    /// The sum of the priorities of all items.
+    pub fn priority_sum(self) -> usize {
+        self.items().map(|i| priority(i).unwrap() as usize).sum()
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        self.intersection(other)
+    }
+}
+
+impl BitOr for ItemSet {
+    type Output = ItemSet;
+
+    fn bitor(self, other: ItemSet) -> ItemSet {
+        self.union(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::collections::BTreeSet;
+
+    #[test]
+    fn test_priority() {
+        assert_eq!(priority('a'), Some(1));
+        assert_eq!(priority('z'), Some(26));
+        assert_eq!(priority('A'), Some(27));
+        assert_eq!(priority('Z'), Some(52));
+        for ch in ['@', '[', '`', '{', '0', ' ', 'é'] {
+            assert_eq!(priority(ch), None, "{:?}", ch);
+        }
+        for p in 1..=52 {
+            assert_eq!(priority(item(p)), Some(p));
+        }
+    }
+
+    #[test]
+    fn test_item_set() {
+        let first = ItemSet::from_items("vJrwpWtwJgWr").unwrap();
+        let second = ItemSet::from_items("hcsFMMfFFhFp").unwrap();
+        assert_eq!((first & second).items().collect::<String>(), "p");
+        assert_eq!((first & second).priority_sum(), 16);
+        assert_eq!(first.len(), 8);
+        assert!(first.contains('J') && !first.contains('j'));
+        assert!((first | second).contains('M'));
+        assert_eq!(ItemSet::ALL.len(), 52);
+        assert!(ItemSet::EMPTY.is_empty());
+
+        let mut set = ItemSet::EMPTY;
+        assert!(set.insert('q'));
+        assert!(!set.insert('q'));
+        assert!(!set.insert('1'));
+        assert_eq!(set.len(), 1);
+        assert_eq!(ItemSet::from_items("abc1d"), Err((3, '1')));
+        assert_eq!(ItemSet::from_items("aéb"), Err((1, 'é')));
+    }
+
+    fn letters() -> impl Strategy<Value = String> {
+        "[a-zA-Z]{0,30}"
+    }
+
+    proptest! {
+        #[test]
+        fn prop_matches_btree_set(a in letters(), b in letters()) {
+            let (x, y) = (ItemSet::from_items(&a).unwrap(), ItemSet::from_items(&b).unwrap());
+            let (p, q): (BTreeSet<u8>, BTreeSet<u8>) = (
+                a.chars().filter_map(priority).collect(),
+                b.chars().filter_map(priority).collect(),
+            );
+            let priorities = |set: ItemSet| set.items().filter_map(priority).collect::<Vec<u8>>();
+            prop_assert_eq!(priorities(x & y), p.intersection(&q).copied().collect::<Vec<u8>>());
+            prop_assert_eq!(priorities(x | y), p.union(&q).copied().collect::<Vec<u8>>());
+            prop_assert_eq!(x.len(), p.len());
+        }
+    }
+}
@@ -0,0 +1,188 @@
+use aoc_common::{Answer, ParseError, Solution};
+use items::ItemSet;
+
+pub mod items;
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    type Parsed = Vec<Rucksack>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed).into()
+    }
+}
+
+/// The items in each of a rucksack's two compartments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rucksack {
+    pub first: ItemSet,
+    pub second: ItemSet,
+}
+
+impl Rucksack {
+    pub fn items(&self) -> ItemSet {
+        self.first | self.second
+    }
+
+    /// The items packed into both compartments.
+    pub fn misplaced(&self) -> ItemSet {
+        self.first & self.second
+    }
+}
+
+/// Reads one rucksack per line and checks what the puzzle promises: exactly one item is in
+/// both compartments of each rucksack, and exactly one is carried by all three elves of each
+/// group.
+fn parse_input(input: &str) -> Result<Vec<Rucksack>, ParseError> {
+    let mut rucksacks: Vec<Rucksack> = Vec::new();
+    // the first line of the group being read, where group errors point
+    let mut group_start: (usize, &str) = (0, "");
+    for (idx, line) in input.lines().enumerate() {
+        if idx % 3 == 0 {
+            group_start = (idx, line);
+        }
+        // a split inside a multi-byte character leaves the whole line in the first
+        // compartment, where that character is reported as not being an item
+        let (first, second) = line.split_at_checked(line.len() / 2).unwrap_or((line, ""));
+        let compartment = |items: &str, start: usize| {
+            ItemSet::from_items(items).map_err(|(offset, ch)| {
+                let col = start + offset;
+                ParseError::at(
+                    idx + 1,
+                    line,
+                    &line[col..col + ch.len_utf8()],
+                    "an item letter `a`-`z` or `A`-`Z`",
+                )
+            })
+        };
+        let rucksack = Rucksack {
+            first: compartment(first, 0)?,
+            second: compartment(second, first.len())?,
+        };
+        if !line.len().is_multiple_of(2) {
+            return Err(ParseError::at(
+                idx + 1,
+                line,
+                line,
+                "an even number of items",
+            ));
+        }
+        if rucksack.misplaced().len() != 1 {
+            return Err(ParseError::at(
+                idx + 1,
+                line,
+                line,
+                "exactly one item in both compartments",
+            ));
+        }
+        rucksacks.push(rucksack);
+        if idx % 3 == 2 && badge(&rucksacks[idx - 2..]).len() != 1 {
+            let (start, line) = group_start;
+            return Err(ParseError::at(
+                start + 1,
+                line,
+                line,
+                "exactly one item carried by the whole group",
+            ));
+        }
+    }
+    if !rucksacks.len().is_multiple_of(3) {
+        return Err(ParseError::end_of_input(
+            rucksacks.len() + 1,
+            "groups of three rucksacks",
+        ));
+    }
+    Ok(rucksacks)
+}
+
+/// The items all rucksacks of `group` have in common.
+fn badge(group: &[Rucksack]) -> ItemSet {
+    group
+        .iter()
+        .fold(ItemSet::ALL, |common, rucksack| common & rucksack.items())
+}
+
+#[allow(clippy::ptr_arg)]
+fn part1(input: &Vec<Rucksack>) -> usize {
+    input
+        .iter()
+        .map(|rucksack| rucksack.misplaced().priority_sum())
+        .sum()
+}
+
+#[allow(clippy::ptr_arg)]
+fn part2(input: &Vec<Rucksack>) -> usize {
+    input
+        .chunks(3)
+        .map(|group| badge(group).priority_sum())
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_errors() {
+        let group = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n";
+        assert_eq!(
+            parse_input(&format!("{}PmmdzqPr1vPwwTWBwg", group)),
+            Err(ParseError::new(
+                3,
+                9,
+                "1",
+                "an item letter `a`-`z` or `A`-`Z`"
+            ))
+        );
+        assert_eq!(
+            parse_input("aéb"),
+            Err(ParseError::new(
+                1,
+                2,
+                "é",
+                "an item letter `a`-`z` or `A`-`Z`"
+            ))
+        );
+        assert_eq!(
+            parse_input("abcd"),
+            Err(ParseError::new(
+                1,
+                1,
+                "abcd",
+                "exactly one item in both compartments"
+            ))
+        );
+        assert_eq!(
+            parse_input("abcabd"),
+            Err(ParseError::new(
+                1,
+                1,
+                "abcabd",
+                "exactly one item in both compartments"
+            ))
+        );
+        assert_eq!(
+            parse_input(&format!("{}PmmdzqPrVvPwwTWBwg\nabac\nabad\nabae", group)),
+            Err(ParseError::new(
+                4,
+                1,
+                "abac",
+                "exactly one item carried by the whole group"
+            ))
+        );
+        assert_eq!(
+            parse_input(group),
+            Err(ParseError::end_of_input(3, "groups of three rucksacks"))
+        );
+    }
+}
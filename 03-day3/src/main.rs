@@ -1,38 +1,7 @@
-use std::fs;
+use aoc_common::solution;
+use day03::Day03;
+use std::process::ExitCode;
 
-fn main() {
-    let input: Vec<String> = fs::read_to_string("input")
-        .unwrap()
-        .lines()
-        .map(|l| l.to_string())
-        .collect();
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-}
-
-fn part1(input: &Vec<String>) -> usize {
-    let mut ret: usize = 0;
-    for line in input {
-        let (first_comp, second_comp): (&str, &str) = line.split_at(line.len() / 2);
-        for char in first_comp.chars() {
-            if second_comp.contains(char) {
-                ret += ((char as u8 - 38) % 58) as usize;
-                break;
-            }
-        }
-    }
-    return ret;
-}
-
-fn part2(input: &Vec<String>) -> usize {
-    let mut ret: usize = 0;
-    for group in input.chunks(3) {
-        for char in group[0].chars() {
-            if group[1].contains(char) && group[2].contains(char) {
-                ret += ((char as u8 - 38) % 58) as usize;
-                break;
-            }
-        }
-    }
-    return ret;
+fn main() -> ExitCode {
+    solution::main(&Day03, concat!(env!("CARGO_MANIFEST_DIR"), "/input"))
 }
@@ -0,0 +1,115 @@
+use aoc_common::error::parse_token;
+use aoc_common::{Answer, ParseError, Solution};
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    type Parsed = Vec<(Range, Range)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed).into()
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<(Range, Range)>, ParseError> {
+    let mut pairs: Vec<(Range, Range)> = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        let Some((first, second)) = line.split_once(',') else {
+            return Err(ParseError::at(idx + 1, line, line, "`<lo>-<hi>,<lo>-<hi>`"));
+        };
+        pairs.push((
+            parse_range(idx + 1, line, first)?,
+            parse_range(idx + 1, line, second)?,
+        ));
+    }
+    Ok(pairs)
+}
+
+fn parse_range(line_no: usize, line: &str, range: &str) -> Result<Range, ParseError> {
+    let Some((lo, hi)) = range.split_once('-') else {
+        return Err(ParseError::at(line_no, line, range, "`<lo>-<hi>`"));
+    };
+    Ok(Range {
+        lo: parse_token(line_no, line, lo, "a section number")?,
+        hi: parse_token(line_no, line, hi, "a section number")?,
+    })
+}
+
+#[allow(clippy::needless_return)]
+fn part1(input: &Vec<(Range, Range)>) -> usize {
+    let mut ret: usize = 0;
+    for (ran_f, ran_s) in input {
+        if (ran_f.lo <= ran_s.lo && ran_f.hi >= ran_s.hi)
+            || (ran_s.lo <= ran_f.lo && ran_s.hi >= ran_f.hi)
+        {
+            ret += 1;
+        }
+    }
+    return ret;
+}
+
+#[allow(clippy::needless_return)]
+fn part2(input: &Vec<(Range, Range)>) -> usize {
+    let mut ret: usize = 0;
+    for (ran_f, ran_s) in input {
+        if ran_f.hi >= ran_s.lo && ran_f.lo <= ran_s.hi {
+            ret += 1;
+        }
+    }
+    return ret;
+}
+
+pub struct Range {
+    lo: usize,
+    hi: usize,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    /// Section bounds `(lo, hi)` of both elves of a pair.
+    type Bounds = ((usize, usize), (usize, usize));
+
+    fn range() -> impl Strategy<Value = (usize, usize)> {
+        (1..100usize, 0..20usize).prop_map(|(lo, len)| (lo, lo + len))
+    }
+
+    fn to_input(pairs: &[Bounds]) -> String {
+        pairs
+            .iter()
+            .map(|((a, b), (c, d))| format!("{}-{},{}-{}\n", a, b, c, d))
+            .collect()
+    }
+
+    proptest! {
+        #[test]
+        fn prop_parse_round_trips(pairs in prop::collection::vec((range(), range()), 0..50)) {
+            let parsed = parse_input(&to_input(&pairs)).unwrap();
+            let bounds: Vec<Bounds> = parsed
+                .iter()
+                .map(|(f, s)| ((f.lo, f.hi), (s.lo, s.hi)))
+                .collect();
+            prop_assert_eq!(bounds, pairs);
+        }
+
+        #[test]
+        fn prop_containment_implies_overlap(
+            pairs in prop::collection::vec((range(), range()), 0..50)
+        ) {
+            let parsed = parse_input(&to_input(&pairs)).unwrap();
+            prop_assert!(part1(&parsed) <= part2(&parsed));
+            prop_assert!(part2(&parsed) <= parsed.len());
+        }
+    }
+}
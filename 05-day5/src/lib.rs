@@ -0,0 +1,210 @@
+use aoc_common::error::parse_token;
+use aoc_common::{Answer, ParseError, Solution};
+use regex::Regex;
+use std::collections::{HashMap, VecDeque};
+
+/// Crate stacks by number, top crate first.
+pub type Stacks = HashMap<usize, VecDeque<char>>;
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    type Parsed = (Stacks, Vec<Operation>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed.0.clone(), parsed.1.clone()).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed.0.clone(), parsed.1.clone()).into()
+    }
+}
+
+#[allow(clippy::needless_return)]
+fn parse_input(input: &str) -> Result<(Stacks, Vec<Operation>), ParseError> {
+    let mut parts = input.split("\n\n");
+    let stacks_str = parts.next().unwrap();
+    let mut stacks_list: Vec<&str> = stacks_str.lines().collect();
+    stacks_list.pop(); // remove the last line
+    let mut stacks: Stacks = HashMap::new();
+    for (idx, line) in stacks_list.iter().enumerate() {
+        if let Some((col, ch)) = line.char_indices().find(|(_, c)| !c.is_ascii()) {
+            return Err(ParseError::at(
+                idx + 1,
+                line,
+                &line[col..col + ch.len_utf8()],
+                "a crate `[A]` or blanks",
+            ));
+        }
+        for c_pos in (1..line.len()).step_by(4) {
+            let ch = line.as_bytes()[c_pos] as char;
+            if ch != ' ' {
+                if !ch.is_ascii_alphabetic() {
+                    return Err(ParseError::at(
+                        idx + 1,
+                        line,
+                        &line[c_pos..c_pos + 1],
+                        "a crate letter",
+                    ));
+                }
+                stacks
+                    .entry(c_pos / 4 + 1)
+                    .and_modify(|s| s.push_back(ch))
+                    .or_insert(VecDeque::from([ch]));
+            }
+        }
+    }
+
+    let first_op_line = stacks_str.lines().count() + 2;
+    let Some(ops_str) = parts.next() else {
+        return Err(ParseError::end_of_input(
+            first_op_line - 1,
+            "a blank line followed by the rearrangement procedure",
+        ));
+    };
+    let mut ops: Vec<Operation> = Vec::new();
+    let re_op = Regex::new(r"move (?P<cnt>\d+) from (?P<fro>\d+) to (?P<to>\d+)").unwrap();
+    let mut numbers: Vec<usize> = stacks.keys().copied().collect();
+    numbers.sort();
+    let stack_expected = format!("a stack number, one of {}", list(&numbers));
+    // how many crates each stack holds after the moves read so far
+    let mut heights: HashMap<usize, usize> = stacks.iter().map(|(k, s)| (*k, s.len())).collect();
+    for (idx, line) in ops_str.lines().enumerate() {
+        let line_no = first_op_line + idx;
+        let Some(matches) = re_op.captures(line) else {
+            return Err(ParseError::at(
+                line_no,
+                line,
+                line,
+                "`move <count> from <stack> to <stack>`",
+            ));
+        };
+        let fro: usize = parse_token(line_no, line, &matches["fro"], &stack_expected)?;
+        let to: usize = parse_token(line_no, line, &matches["to"], &stack_expected)?;
+        for (stack, token) in [(fro, &matches["fro"]), (to, &matches["to"])] {
+            if !stacks.contains_key(&stack) {
+                return Err(ParseError::at(line_no, line, token, &stack_expected));
+            }
+        }
+        let cnt: usize = parse_token(line_no, line, &matches["cnt"], "a crate count")?;
+        if cnt > heights[&fro] {
+            return Err(ParseError::at(
+                line_no,
+                line,
+                &matches["cnt"],
+                &format!("at most the {} crates on stack {}", heights[&fro], fro),
+            ));
+        }
+        *heights.get_mut(&fro).unwrap() -= cnt;
+        *heights.get_mut(&to).unwrap() += cnt;
+        ops.push(Operation { cnt, fro, to });
+    }
+
+    return Ok((stacks, ops));
+}
+
+#[derive(Debug, Clone)]
+pub struct Operation {
+    cnt: usize,
+    fro: usize,
+    to: usize,
+}
+
+/// "1, 2 or 3"
+fn list(numbers: &[usize]) -> String {
+    let strings: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+    match strings.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} or {}", rest.join(", "), last),
+        _ => strings.concat(),
+    }
+}
+
+/// The top crate of each stack in stack order; empty stacks have none.
+fn tops(stacks: &Stacks) -> String {
+    let mut numbers: Vec<&usize> = stacks.keys().collect();
+    numbers.sort();
+    numbers
+        .into_iter()
+        .filter_map(|key| stacks[key].front())
+        .collect()
+}
+
+#[allow(clippy::needless_return)]
+fn part1(mut stacks: Stacks, ops: Vec<Operation>) -> String {
+    for op in ops {
+        for _ in 0..op.cnt {
+            let Some(elem) = stacks.get_mut(&op.fro).and_then(|s| s.pop_front()) else {
+                break;
+            };
+            stacks.entry(op.to).or_default().push_front(elem);
+        }
+    }
+    return tops(&stacks);
+}
+
+#[allow(clippy::needless_return)]
+fn part2(mut stacks: Stacks, ops: Vec<Operation>) -> String {
+    for op in ops {
+        let Some(from) = stacks.get_mut(&op.fro) else {
+            continue;
+        };
+        let cnt = op.cnt.min(from.len());
+        let mov_stack: Vec<char> = from.drain(..cnt).collect();
+        let to = stacks.entry(op.to).or_default();
+        for elem in mov_stack.into_iter().rev() {
+            to.push_front(elem);
+        }
+    }
+    return tops(&stacks);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STACKS: &str = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n";
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_input(&format!("{}move 1 from 2 to 4", STACKS)).err(),
+            Some(ParseError::new(
+                6,
+                18,
+                "4",
+                "a stack number, one of 1, 2 or 3"
+            ))
+        );
+        assert_eq!(
+            parse_input(&format!("{}move 2 from 3 to 1", STACKS)).err(),
+            Some(ParseError::new(
+                6,
+                6,
+                "2",
+                "at most the 1 crates on stack 3"
+            ))
+        );
+        // stack 3 is empty after the first move
+        assert_eq!(
+            parse_input(&format!("{}move 1 from 3 to 1\nmove 1 from 3 to 2", STACKS)).err(),
+            Some(ParseError::new(
+                7,
+                6,
+                "1",
+                "at most the 0 crates on stack 3"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_empty_stack() {
+        let (stacks, ops) = parse_input(&format!("{}move 1 from 3 to 1", STACKS)).unwrap();
+        assert_eq!(part1(stacks.clone(), ops.clone()), "PD");
+        assert_eq!(part2(stacks, ops), "PD");
+    }
+}
@@ -0,0 +1,119 @@
+use aoc_common::{Answer, ParseError, Solution};
+use std::collections::{HashSet, VecDeque};
+use std::hash::Hash;
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    type Parsed = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Ok(input.to_string())
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        solution(parsed, 4).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        solution(parsed, 14).into()
+    }
+}
+
+#[allow(clippy::needless_return, clippy::ptr_arg)]
+fn solution(input: &String, num_unique: usize) -> usize {
+    let mut buf: VecDeque<char> = VecDeque::new();
+    for (idx, ch) in input.chars().enumerate() {
+        buf.push_back(ch);
+        if buf.len() < num_unique {
+            continue;
+        } else if buf.len() > num_unique {
+            buf.pop_front();
+        }
+        if has_unique_elements(buf.clone()) {
+            return idx + 1;
+        }
+    }
+    return 0;
+}
+
+fn has_unique_elements<T>(iter: T) -> bool
+where
+    T: IntoIterator,
+    T::Item: Eq + Hash,
+{
+    let mut unique = HashSet::new();
+    iter.into_iter().all(move |x| unique.insert(x))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    proptest! {
+        #[test]
+        fn prop_finds_the_first_unique_window(input in "[a-f]{0,40}", n in 1..7usize) {
+            let chars: Vec<char> = input.chars().collect();
+            let unique_until = |end: usize| has_unique_elements(chars[end - n..end].iter());
+            let found = solution(&input, n);
+            if found == 0 {
+                prop_assert!((n..=chars.len()).all(|end| !unique_until(end)));
+            } else {
+                prop_assert!(found >= n && unique_until(found));
+                prop_assert!((n..found).all(|end| !unique_until(end)));
+            }
+        }
+    }
+
+    #[test]
+    fn string1() {
+        assert_eq!(
+            solution(&"mjqjpqmgbljsphdztnvjfqwrcgsmlb".to_string(), 4),
+            7
+        );
+        assert_eq!(
+            solution(&"mjqjpqmgbljsphdztnvjfqwrcgsmlb".to_string(), 14),
+            19
+        );
+    }
+    #[test]
+    fn string2() {
+        assert_eq!(solution(&"bvwbjplbgvbhsrlpgdmjqwftvncz".to_string(), 4), 5);
+        assert_eq!(
+            solution(&"bvwbjplbgvbhsrlpgdmjqwftvncz".to_string(), 14),
+            23
+        );
+    }
+    #[test]
+    fn string3() {
+        assert_eq!(solution(&"nppdvjthqldpwncqszvftbrmjlhg".to_string(), 4), 6);
+        assert_eq!(
+            solution(&"nppdvjthqldpwncqszvftbrmjlhg".to_string(), 14),
+            23
+        );
+    }
+    #[test]
+    fn string4() {
+        assert_eq!(
+            solution(&"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".to_string(), 4),
+            10
+        );
+        assert_eq!(
+            solution(&"nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg".to_string(), 14),
+            29
+        );
+    }
+    #[test]
+    fn string5() {
+        assert_eq!(
+            solution(&"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".to_string(), 4),
+            11
+        );
+        assert_eq!(
+            solution(&"zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw".to_string(), 14),
+            26
+        );
+    }
+}
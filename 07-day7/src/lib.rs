@@ -0,0 +1,276 @@
+use aoc_common::error::parse_token;
+use aoc_common::{input, Answer, ParseError, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    type Parsed = HashMap<String, HashSet<String>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        get_disk(&input::lines(input))
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed).into()
+    }
+}
+
+lazy_static! {
+    static ref REGEX_CMD: Regex = Regex::new(r"\$\s+(?P<cmd>\w+)(:?\s+)?(?P<arg>.*)?").unwrap();
+    static ref REGEX_DIR: Regex = Regex::new(r"dir\s(?P<dir>.+)").unwrap();
+    static ref REGEX_FILE: Regex =
+        Regex::new(r"(?P<size>\d+)\s(?P<name>[^0-9\.])(:?\.+)?(?P<type>\D+)?").unwrap();
+}
+
+/// The size of the disk; the files on it can't add up to more.
+const TOTAL_SPACE: usize = 70_000_000;
+
+#[allow(clippy::needless_return, clippy::ptr_arg)]
+fn get_disk(input: &Vec<String>) -> Result<HashMap<String, HashSet<String>>, ParseError> {
+    let mut cwd: Vec<String> = Vec::new();
+    let mut lines = input.iter().enumerate();
+    let mut curr_line = lines.next();
+    let mut disk: HashMap<String, HashSet<String>> = HashMap::new();
+    // the sizes of all different files listed so far
+    let mut used: usize = 0;
+
+    while let Some((idx, line)) = curr_line {
+        let Some(capture) = REGEX_CMD.captures(line) else {
+            return Err(ParseError::at(
+                idx + 1,
+                line,
+                line,
+                "a command `$ cd` or `$ ls`",
+            ));
+        };
+        let cmd = capture.name("cmd").unwrap().as_str();
+        match cmd {
+            "cd" => {
+                let arg = &capture["arg"];
+                if arg == ".." {
+                    // like a shell, `cd ..` in the root stays there
+                    if cwd.len() > 1 {
+                        cwd.pop();
+                    }
+                } else if arg == "/" {
+                    cwd = Vec::from(["".to_string()]);
+                    disk.entry(cwd.join("/") + "/").or_default();
+                } else {
+                    let Some(dir) = disk.get_mut(&(cwd.join("/") + "/")) else {
+                        return Err(ParseError::at(idx + 1, line, line, "`$ cd /` first"));
+                    };
+                    dir.insert("dir ".to_owned() + &cwd.join("/") + "/" + arg + "/");
+                    cwd.push(arg.to_string());
+                    disk.entry(cwd.join("/") + "/").or_default();
+                }
+                curr_line = lines.next();
+            }
+            "ls" => {
+                let Some(dir) = disk.get_mut(&(cwd.join("/") + "/")) else {
+                    return Err(ParseError::at(idx + 1, line, line, "`$ cd /` first"));
+                };
+                let mut listed: Vec<String> = Vec::new();
+                loop {
+                    curr_line = lines.next();
+                    let Some((idx, entry)) = curr_line else {
+                        break;
+                    };
+                    if entry.starts_with('$') {
+                        break;
+                    }
+                    if let Some(directory) = REGEX_DIR.captures(entry) {
+                        let path = cwd.join("/") + "/" + &directory["dir"] + "/";
+                        dir.insert("dir ".to_owned() + &path);
+                        listed.push(path);
+                    } else {
+                        check_file(idx + 1, entry)?;
+                        if !dir.insert(entry.to_string()) {
+                            continue;
+                        }
+                        if let Some(file) = REGEX_FILE.captures(entry) {
+                            let size: usize =
+                                parse_token(idx + 1, entry, &file["size"], "a file size")?;
+                            used = match used.checked_add(size) {
+                                Some(total) if total <= TOTAL_SPACE => total,
+                                _ => {
+                                    return Err(ParseError::at(
+                                        idx + 1,
+                                        entry,
+                                        &file["size"],
+                                        &format!(
+                                            "a file that fits into the {} bytes left on the disk",
+                                            TOTAL_SPACE - used
+                                        ),
+                                    ))
+                                }
+                            };
+                        }
+                    }
+                }
+                // directories that are listed but never entered are empty
+                for path in listed {
+                    disk.entry(path).or_default();
+                }
+            }
+            _ => {
+                return Err(ParseError::at(
+                    idx + 1,
+                    line,
+                    cmd,
+                    "a command `$ cd` or `$ ls`",
+                ))
+            }
+        }
+    }
+    if disk.is_empty() {
+        return Err(ParseError::end_of_input(input.len() + 1, "`$ cd /`"));
+    }
+    return Ok(disk);
+}
+
+/// Makes sure an `ls` entry that is not a directory has the `<size> <name>` form.
+fn check_file(line_no: usize, entry: &str) -> Result<(), ParseError> {
+    let expected = "`<size> <name>` or `dir <name>`";
+    match entry.split_once(' ') {
+        Some((size, name)) if !name.is_empty() => {
+            parse_token::<usize>(line_no, entry, size, "a file size")?;
+            Ok(())
+        }
+        _ => Err(ParseError::at(line_no, entry, entry, expected)),
+    }
+}
+
+#[allow(clippy::needless_return)]
+fn calc_dir_size(
+    dir_sizes: &HashMap<String, usize>,
+    disk: &HashMap<String, HashSet<String>>,
+    curr_dir: String,
+) -> usize {
+    let mut size = 0;
+    let dir = &disk[&curr_dir];
+    for cont in dir {
+        if let Some(file) = REGEX_FILE.captures(cont) {
+            size += file["size"].parse::<usize>().unwrap();
+        } else if let Some(directory) = REGEX_DIR.captures(cont) {
+            if let Some(dir_size) = dir_sizes.get(&directory["dir"].to_string()) {
+                size += dir_size;
+            } else {
+                size += calc_dir_size(dir_sizes, disk, directory["dir"].to_string());
+            }
+        }
+    }
+    return size;
+}
+
+#[allow(clippy::needless_return)]
+fn part1(disk: &HashMap<String, HashSet<String>>) -> usize {
+    let mut dir_sizes: HashMap<String, usize> = HashMap::new();
+    let mut ret = 0;
+    for cd in disk.keys() {
+        let size = calc_dir_size(&dir_sizes, disk, cd.to_string());
+        dir_sizes.insert(cd.to_string(), size);
+        if size <= 100_000 {
+            ret += size;
+        }
+    }
+    return ret;
+}
+
+#[allow(clippy::needless_return)]
+fn part2(disk: &HashMap<String, HashSet<String>>) -> usize {
+    let mut dir_sizes: HashMap<String, usize> = HashMap::new();
+    for cd in disk.keys() {
+        let size = calc_dir_size(&dir_sizes, disk, cd.to_string());
+        dir_sizes.insert(cd.to_string(), size);
+    }
+    let mut dir_choices: Vec<usize> = Vec::new();
+    let total_used = dir_sizes["/"];
+    let needed_space: usize = 30_000_000;
+    for (_name, size) in dir_sizes {
+        // the parser makes sure the files fit, so deleting `/` always frees enough
+        if TOTAL_SPACE.saturating_sub(total_used) + size > needed_space {
+            dir_choices.push(size);
+        }
+    }
+    return *dir_choices
+        .iter()
+        .min()
+        .expect("deleting / frees enough space");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part1() {
+        let input: Vec<String> = input::lines(&input::read_input("sample").unwrap());
+        let disk: HashMap<String, HashSet<String>> = get_disk(&input).unwrap();
+        assert_eq!(part1(&disk), 95437);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input: Vec<String> = input::lines(&input::read_input("sample").unwrap());
+        let disk: HashMap<String, HashSet<String>> = get_disk(&input).unwrap();
+        assert_eq!(part2(&disk), 24933642);
+    }
+
+    #[test]
+    fn test_cd_up_in_root() {
+        let disk = get_disk(&input::lines("$ cd /\n$ cd ..\n$ cd a\n$ ls\n5 f")).unwrap();
+        assert!(disk["/a/"].contains("5 f"));
+        assert_eq!(part1(&disk), 10);
+    }
+
+    #[test]
+    fn test_listed_but_not_entered() {
+        let disk = get_disk(&input::lines("$ cd /\n$ ls\ndir a\n14 b")).unwrap();
+        assert!(disk["/a/"].is_empty());
+        assert_eq!(part1(&disk), 14);
+    }
+
+    #[test]
+    fn test_full_disk() {
+        // listing a directory twice doesn't count its files twice
+        let input = "$ cd /\n$ ls\n40000000 a\n$ ls\n40000000 a\n$ cd ..\n$ ls\n30000000 b";
+        let disk = get_disk(&input::lines(input)).unwrap();
+        assert_eq!(part2(&disk), 70000000);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let input: Vec<String> = input::lines("$ cd /\n$ pwd");
+        assert_eq!(
+            get_disk(&input),
+            Err(ParseError::new(2, 3, "pwd", "a command `$ cd` or `$ ls`"))
+        );
+        let input: Vec<String> = input::lines("$ cd /\n$ ls\nabc def");
+        assert_eq!(
+            get_disk(&input),
+            Err(ParseError::new(3, 1, "abc", "a file size"))
+        );
+        let input: Vec<String> = input::lines("$ cd /\n$ ls\n40000000 a\n30000001 b");
+        assert_eq!(
+            get_disk(&input),
+            Err(ParseError::new(
+                4,
+                1,
+                "30000001",
+                "a file that fits into the 30000000 bytes left on the disk"
+            ))
+        );
+        assert_eq!(
+            get_disk(&input::lines("")),
+            Err(ParseError::end_of_input(1, "`$ cd /`"))
+        );
+    }
+}
@@ -0,0 +1,191 @@
+use aoc_common::grid::DIRECTIONS4;
+use aoc_common::image::{self, Image};
+use aoc_common::visualize::{self, Colour, Glyph, Visualize};
+use aoc_common::{Answer, Coordinate, Grid, ParseError, Solution};
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    type Parsed = Grid<Tree>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        gen_map(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed).into()
+    }
+}
+
+fn part1(map: &Grid<Tree>) -> usize {
+    visualize::frame(&Visibility(map));
+    image::save("heights", || heights_image(map));
+    map.iter().filter(|(_, tree)| tree.is_visible()).count()
+}
+
+#[allow(clippy::needless_return)]
+fn part2(map: &Grid<Tree>) -> usize {
+    let scores: Grid<usize> = Grid::from_vec(
+        map.width(),
+        map.coordinates().map(|c| scenic_score(map, c)).collect(),
+    );
+    let best = scores.iter().map(|(_, &s)| s).max().unwrap();
+    image::save("scenic", || scenic_image(&scores, best));
+    return best;
+}
+
+/// Tree heights from dark blue (0) to pale yellow (9).
+fn heights_image(map: &Grid<Tree>) -> Image {
+    Image::from_fn(map.width(), map.height(), |c| {
+        image::gradient(map[c].height as f64 / 9.0)
+    })
+    .scaled(4)
+}
+
+/// Scenic scores on a logarithmic scale up to `best`, which most trees are far from.
+fn scenic_image(scores: &Grid<usize>, best: usize) -> Image {
+    let max = (best as f64 + 1.0).ln();
+    Image::from_fn(scores.width(), scores.height(), |c| {
+        image::gradient((scores[c] as f64 + 1.0).ln() / max)
+    })
+    .scaled(4)
+}
+
+/// Product of the viewing distances in all four directions.
+fn scenic_score(map: &Grid<Tree>, c: Coordinate) -> usize {
+    DIRECTIONS4.iter().map(|&d| explore(map, c, d)).product()
+}
+
+/// Number of trees visible from `c` looking in direction `d`, up to and including the first
+/// one that is at least as high.
+fn explore(map: &Grid<Tree>, c: Coordinate, d: Coordinate) -> usize {
+    let h: u32 = map[c].height;
+    let mut i: usize = 0;
+    for (_, t) in map.ray(c, d) {
+        i += 1;
+        if t.height >= h {
+            break;
+        }
+    }
+    i
+}
+
+#[allow(clippy::needless_return)]
+fn gen_map(input: &str) -> Result<Grid<Tree>, ParseError> {
+    let heights: Grid<u32> = Grid::parse(input, "a tree height `0`-`9`", |ch| ch.to_digit(10))?;
+    // a tree is visible from a direction if every tree between it and that edge is lower
+    let visible = |c: Coordinate, d: Coordinate| heights.ray(c, d).all(|(_, h)| *h < heights[c]);
+    let mut map: Grid<Tree> = heights.map(|&height| Tree {
+        height,
+        vis_n: false,
+        vis_e: false,
+        vis_s: false,
+        vis_w: false,
+    });
+    for c in heights.coordinates() {
+        let tree = &mut map[c];
+        tree.vis_n = visible(c, DIRECTIONS4[0]);
+        tree.vis_e = visible(c, DIRECTIONS4[1]);
+        tree.vis_s = visible(c, DIRECTIONS4[2]);
+        tree.vis_w = visible(c, DIRECTIONS4[3]);
+    }
+    return Ok(map);
+}
+
+pub struct Tree {
+    height: u32,
+    vis_n: bool,
+    vis_e: bool,
+    vis_s: bool,
+    vis_w: bool,
+}
+
+impl Tree {
+    /// Whether the tree can be seen from outside the grid in any direction.
+    fn is_visible(&self) -> bool {
+        self.vis_n || self.vis_e || self.vis_s || self.vis_w
+    }
+}
+
+/// The forest as tree heights, green where a tree is visible from outside and grey where it
+/// is hidden.
+pub struct Visibility<'a>(pub &'a Grid<Tree>);
+
+impl Visualize for Visibility<'_> {
+    fn bounds(&self) -> (Coordinate, Coordinate) {
+        (
+            Coordinate::new(0, 0),
+            Coordinate::new(self.0.width() as isize - 1, self.0.height() as isize - 1),
+        )
+    }
+
+    fn glyph(&self, c: Coordinate) -> Glyph {
+        let tree = &self.0[c];
+        let height = char::from_digit(tree.height, 10).unwrap();
+        if tree.is_visible() {
+            Glyph::new(height, Colour::Green)
+        } else {
+            Glyph::new(height, Colour::Grey)
+        }
+    }
+
+    fn caption(&self) -> String {
+        let visible = self.0.iter().filter(|(_, t)| t.is_visible()).count();
+        format!("{} trees visible from outside", visible)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::input;
+
+    #[test]
+    fn test_part1() {
+        let map = gen_map(&input::read_input("sample").unwrap()).unwrap();
+
+        assert_eq!(part1(&map), 21);
+    }
+
+    #[test]
+    fn test_visualize() {
+        let map = gen_map(&input::read_input("sample").unwrap()).unwrap();
+        let visibility = Visibility(&map);
+        let hidden: Vec<Coordinate> = map
+            .coordinates()
+            .filter(|&c| visibility.glyph(c).colour == Colour::Grey)
+            .collect();
+        assert_eq!(
+            hidden,
+            vec![
+                Coordinate::new(3, 1),
+                Coordinate::new(2, 2),
+                Coordinate::new(1, 3),
+                Coordinate::new(3, 3)
+            ]
+        );
+        assert_eq!(visibility.glyph(Coordinate::new(1, 1)).symbol, '5');
+        assert_eq!(visibility.caption(), "21 trees visible from outside");
+    }
+
+    #[test]
+    fn test_part2() {
+        let map = gen_map(&input::read_input("sample").unwrap()).unwrap();
+
+        assert_eq!(part2(&map), 8);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            gen_map("303\n2a5\n653").err(),
+            Some(ParseError::new(2, 2, "a", "a tree height `0`-`9`"))
+        );
+        assert_eq!(gen_map("303\n25\n653").err().map(|e| e.line), Some(2));
+    }
+}
@@ -0,0 +1,333 @@
+use aoc_common::error::parse_token;
+use aoc_common::grid;
+use aoc_common::visualize::{self, Colour, Glyph, Visualize};
+use aoc_common::{Answer, Coordinate, ParseError, Solution};
+use std::collections::HashSet;
+use tracing::{debug, trace};
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    type Parsed = Vec<(char, isize)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed).into()
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<(char, isize)>, ParseError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| get_instruction(idx + 1, line))
+        .collect()
+}
+
+/// A rope of knots on the plane, pulled along by its head one step at a time.
+#[derive(Debug, Clone)]
+pub struct Rope {
+    knots: Vec<Coordinate>,
+}
+
+impl Rope {
+    /// `len` knots (at least one) stacked on the origin.
+    pub fn new(len: usize) -> Rope {
+        Rope {
+            knots: vec![Coordinate { x: 0, y: 0 }; len.max(1)],
+        }
+    }
+
+    pub fn knots(&self) -> &[Coordinate] {
+        &self.knots
+    }
+
+    pub fn head(&self) -> Coordinate {
+        self.knots[0]
+    }
+
+    pub fn tail(&self) -> Coordinate {
+        self.knots[self.knots.len() - 1]
+    }
+
+    /// Moves the head one step `U`, `D`, `L` or `R` and lets every other knot follow.
+    pub fn step(&mut self, direction: char) {
+        match direction {
+            'U' => self.knots[0].y += 1,
+            'D' => self.knots[0].y -= 1,
+            'R' => self.knots[0].x += 1,
+            'L' => self.knots[0].x -= 1,
+            _ => panic!("Unhandled direction: {}", direction),
+        }
+        let rope = &mut self.knots;
+        for i in 1..rope.len() {
+            // Check if we need to move at all
+            let delta_x = rope[i - 1].x - rope[i].x;
+            let delta_y = rope[i - 1].y - rope[i].y;
+            if delta_x.abs() > 1 || delta_y.abs() > 1 {
+                // move diagonally
+                if delta_x != 0 && delta_y != 0 {
+                    rope[i].x += delta_x.signum();
+                    rope[i].y += delta_y.signum();
+                //move straight
+                } else {
+                    rope[i].x += if delta_x.abs() > 1 {
+                        delta_x.signum()
+                    } else {
+                        0
+                    };
+                    rope[i].y += if delta_y.abs() > 1 {
+                        delta_y.signum()
+                    } else {
+                        0
+                    };
+                }
+            } else {
+                // if we reach the first knot that doesn't move, all knots behind it don't move
+                // either.
+                break;
+            }
+        }
+    }
+}
+
+/// A rope together with everywhere its tail has been, drawn like in the puzzle: `H` for the
+/// head, `T` or the knot's number for the others, `#` for visited positions and `s` for the
+/// start. Up is `+y` for the rope but the top of the picture, so rows are the negated `y`.
+pub struct Trail<'a> {
+    pub rope: &'a Rope,
+    pub visited: &'a HashSet<Coordinate>,
+}
+
+impl Trail<'_> {
+    fn on_rope(c: Coordinate) -> Coordinate {
+        Coordinate::new(c.x, -c.y)
+    }
+}
+
+impl Visualize for Trail<'_> {
+    fn bounds(&self) -> (Coordinate, Coordinate) {
+        let (min, max) =
+            grid::bounds(self.visited.iter().chain(self.rope.knots())).unwrap_or_default();
+        (
+            Coordinate::new(min.x, -max.y),
+            Coordinate::new(max.x, -min.y),
+        )
+    }
+
+    fn glyph(&self, c: Coordinate) -> Glyph {
+        let c = Trail::on_rope(c);
+        let knots = self.rope.knots();
+        if let Some(i) = knots.iter().position(|&k| k == c) {
+            return match i {
+                0 => Glyph::new('H', Colour::Red),
+                _ if knots.len() == 2 => Glyph::new('T', Colour::Yellow),
+                _ => Glyph::new(char::from_digit(i as u32 % 36, 36).unwrap(), Colour::Yellow),
+            };
+        }
+        if c == Coordinate::default() {
+            return Glyph::new('s', Colour::Cyan);
+        }
+        if self.visited.contains(&c) {
+            return Glyph::new('#', Colour::Green);
+        }
+        Glyph::new('.', Colour::Grey)
+    }
+
+    fn focus(&self) -> Option<Coordinate> {
+        Some(Trail::on_rope(self.rope.head()))
+    }
+
+    fn caption(&self) -> String {
+        format!("{} positions visited by the tail", self.visited.len())
+    }
+}
+
+/// Number of positions the tail of a rope with `knots` knots visits.
+fn tail_positions(input: &Vec<(char, isize)>, knots: usize) -> usize {
+    let mut rope = Rope::new(knots);
+    let mut visited: HashSet<Coordinate> = HashSet::from([rope.tail()]);
+    for &(direction, len) in input {
+        for _ in 0..len {
+            rope.step(direction);
+            trace!(knots = ?rope.knots(), "step");
+            visited.insert(rope.tail());
+            visualize::frame(&Trail {
+                rope: &rope,
+                visited: &visited,
+            });
+        }
+        debug!(%direction, len, head = ?rope.head(), tail = ?rope.tail(), "moved");
+    }
+    visited.len()
+}
+
+#[allow(clippy::needless_return)]
+fn part1(input: &Vec<(char, isize)>) -> usize {
+    return tail_positions(input, 2);
+}
+
+#[allow(clippy::needless_return)]
+fn part2(input: &Vec<(char, isize)>) -> usize {
+    return tail_positions(input, 10);
+}
+
+/// Moves are bounded so that simulating them stays quick whatever the input says; real
+/// motions stay well below this.
+const MAX_STEPS: usize = 1_000;
+
+#[allow(clippy::needless_return)]
+fn get_instruction(line_no: usize, ins: &str) -> Result<(char, isize), ParseError> {
+    let mut sp = ins.split_whitespace();
+    let c = match sp.next() {
+        Some(d @ ("U" | "D" | "L" | "R")) => d.chars().next().unwrap(),
+        Some(d) => {
+            return Err(ParseError::at(
+                line_no,
+                ins,
+                d,
+                "a direction `U`, `D`, `L` or `R`",
+            ))
+        }
+        None => return Err(ParseError::end_of_input(line_no, "`<direction> <steps>`")),
+    };
+    let expected = format!("a number of steps from 0 to {}", MAX_STEPS);
+    let n: usize = match sp.next() {
+        Some(token) => {
+            let n: usize = parse_token(line_no, ins, token, &expected)?;
+            if n > MAX_STEPS {
+                return Err(ParseError::at(line_no, ins, token, &expected));
+            }
+            n
+        }
+        None => {
+            return Err(ParseError::new(
+                line_no,
+                ins.chars().count() + 1,
+                "",
+                &expected,
+            ))
+        }
+    };
+    return Ok((c, n as isize));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::input;
+    use proptest::prelude::*;
+
+    fn moves() -> impl Strategy<Value = Vec<(char, isize)>> {
+        prop::collection::vec(
+            (prop::sample::select(vec!['U', 'D', 'L', 'R']), 0..10isize),
+            0..40,
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn prop_knots_stay_adjacent(moves in moves(), len in 1..12usize) {
+            let mut rope = Rope::new(len);
+            for (direction, steps) in moves {
+                for _ in 0..steps {
+                    let head = rope.head();
+                    rope.step(direction);
+                    prop_assert_eq!(head.delta(rope.head()).x.abs() + head.delta(rope.head()).y.abs(), 1);
+                    for pair in rope.knots().windows(2) {
+                        let d = pair[0].delta(pair[1]);
+                        prop_assert!(d.x.abs() <= 1 && d.y.abs() <= 1, "{:?}", rope);
+                    }
+                }
+            }
+        }
+
+        #[test]
+        fn prop_parse_round_trips(moves in moves()) {
+            let text: String = moves.iter().map(|(d, n)| format!("{} {}\n", d, n)).collect();
+            prop_assert_eq!(parse_input(&text).unwrap(), moves);
+        }
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(&input::read_input("sample").unwrap()).unwrap();
+        assert_eq!(part1(&input), 13);
+    }
+
+    #[test]
+    fn test_visualize() {
+        let input = parse_input(&input::read_input("sample").unwrap()).unwrap();
+        let mut rope = Rope::new(2);
+        let mut visited: HashSet<Coordinate> = HashSet::from([rope.tail()]);
+        for &(direction, len) in &input {
+            for _ in 0..len {
+                rope.step(direction);
+                visited.insert(rope.tail());
+            }
+        }
+        let trail = Trail {
+            rope: &rope,
+            visited: &visited,
+        };
+        let (from, to) = trail.bounds();
+        assert_eq!(
+            visualize::picture(&trail, from, to, false),
+            "\
+..##.
+...##
+.TH##
+....#
+s###.
+"
+        );
+        assert_eq!(trail.caption(), "13 positions visited by the tail");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(&input::read_input("sample").unwrap()).unwrap();
+        let input2 = parse_input(&input::read_input("sample2").unwrap()).unwrap();
+        assert_eq!(part2(&input), 1);
+        assert_eq!(part2(&input2), 36);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_input("R 4\nX 2"),
+            Err(ParseError::new(
+                2,
+                1,
+                "X",
+                "a direction `U`, `D`, `L` or `R`"
+            ))
+        );
+        assert_eq!(
+            parse_input("R 4\nU four"),
+            Err(ParseError::new(
+                2,
+                3,
+                "four",
+                "a number of steps from 0 to 1000"
+            ))
+        );
+        assert_eq!(
+            parse_input("R 4294967296"),
+            Err(ParseError::new(
+                1,
+                3,
+                "4294967296",
+                "a number of steps from 0 to 1000"
+            ))
+        );
+    }
+}
@@ -0,0 +1,227 @@
+use aoc_common::error::parse_token;
+use aoc_common::image::{self, Image};
+use aoc_common::{Answer, ParseError, Solution};
+use tracing::{debug, trace};
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Parsed = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed).into()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Noop,
+    Addx(isize),
+}
+
+/// The X register is bounded so that signal strengths can't overflow whatever the input says;
+/// real programs keep it near the 40 columns of the CRT.
+const MAX_REGISTER: isize = 1_000;
+
+fn parse_input(input: &str) -> Result<Vec<Instruction>, ParseError> {
+    let mut program: Vec<Instruction> = Vec::new();
+    // the X register after the instructions read so far
+    let mut reg_val: isize = 1;
+    for (idx, line) in input.lines().enumerate() {
+        let mut splits = line.split(' ');
+        let instr: &str = splits.next().unwrap();
+        match (instr, splits.next()) {
+            ("noop", None) => program.push(Instruction::Noop),
+            ("addx", Some(val)) => {
+                let n: isize = parse_token(idx + 1, line, val, "a number to add")?;
+                reg_val = match reg_val.checked_add(n) {
+                    Some(x) if x.abs() <= MAX_REGISTER => x,
+                    _ => {
+                        return Err(ParseError::at(
+                            idx + 1,
+                            line,
+                            val,
+                            &format!(
+                                "a number that keeps X from -{} to {}",
+                                MAX_REGISTER, MAX_REGISTER
+                            ),
+                        ))
+                    }
+                };
+                program.push(Instruction::Addx(n));
+            }
+            _ => {
+                return Err(ParseError::at(
+                    idx + 1,
+                    line,
+                    line,
+                    "`noop` or `addx <value>`",
+                ))
+            }
+        }
+    }
+    Ok(program)
+}
+
+#[allow(clippy::needless_return)]
+fn part1(input: &Vec<Instruction>) -> isize {
+    let mut sum: isize = 0;
+    let mut cycle: isize = 0;
+    let mut reg_val: isize = 1;
+    for instr in input {
+        match *instr {
+            Instruction::Noop => {
+                cycle += 1;
+                if cycle == 20 || (cycle - 20) % 40 == 0 {
+                    sum += reg_val * cycle;
+                    debug!(cycle, strength = reg_val * cycle, "sampled");
+                }
+            }
+            Instruction::Addx(val) => {
+                cycle += 2;
+                if cycle == 20 || cycle == 21 || (cycle - 20) % 40 == 0 || (cycle - 20) % 40 == 1 {
+                    sum += reg_val * (cycle / 20) * 20;
+                    debug!(
+                        cycle = (cycle / 20) * 20,
+                        strength = reg_val * (cycle / 20) * 20,
+                        "sampled"
+                    );
+                }
+                reg_val += val;
+            }
+        }
+        trace!(cycle, x = reg_val, ?instr, "executed");
+    }
+    return sum;
+}
+
+#[allow(clippy::needless_return)]
+fn part2(input: &Vec<Instruction>) -> String {
+    let mut cycle: isize = 0;
+    let mut reg_val: isize = 1;
+    let mut output: String = "".to_string();
+    for instr in input {
+        match *instr {
+            Instruction::Noop => {
+                if (reg_val - cycle % 40).abs() <= 1 {
+                    output.push('#');
+                } else {
+                    output.push('.');
+                }
+                trace!(cycle = cycle + 1, x = reg_val, pixel = %output.chars().last().unwrap(), "drew");
+                if (cycle + 1) % 40 == 0 {
+                    output.push('\n');
+                }
+                cycle += 1;
+            }
+            Instruction::Addx(val) => {
+                for _ in 0..2 {
+                    if (reg_val - cycle % 40).abs() <= 1 {
+                        output.push('#');
+                    } else {
+                        output.push('.');
+                    }
+                    trace!(cycle = cycle + 1, x = reg_val, pixel = %output.chars().last().unwrap(), "drew");
+                    if (cycle + 1) % 40 == 0 && cycle != 0 {
+                        output.push('\n');
+                    }
+                    cycle += 1;
+                }
+                reg_val += val;
+            }
+        }
+    }
+    output.pop();
+    image::save("crt", || crt_image(&output));
+    return output;
+}
+
+/// The picture on the CRT, lit pixels green on black, each one blown up to a square so the
+/// letters are readable.
+fn crt_image(screen: &str) -> Image {
+    let rows: Vec<&[u8]> = screen.lines().map(str::as_bytes).collect();
+    let width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
+    Image::from_fn(width, rows.len(), |c| {
+        match rows[c.y as usize].get(c.x as usize) {
+            Some(b'#') => [120, 255, 120],
+            _ => [10, 20, 10],
+        }
+    })
+    .scaled(8)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use aoc_common::input;
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(&input::read_input("sample").unwrap()).unwrap();
+
+        assert_eq!(part1(&input), 13140);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(&input::read_input("sample").unwrap()).unwrap();
+        assert_eq!(
+            part2(&input),
+            "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######....."
+                .to_string()
+        );
+    }
+
+    #[test]
+    fn test_crt_image() {
+        let image = crt_image("#.\n.#");
+        assert_eq!((image.width(), image.height()), (16, 16));
+        assert_eq!(image.get(7, 7), Some([120, 255, 120]));
+        assert_eq!(image.get(8, 7), Some([10, 20, 10]));
+        assert_eq!(image.get(8, 8), Some([120, 255, 120]));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_input("noop\naddx x"),
+            Err(ParseError::new(2, 6, "x", "a number to add"))
+        );
+        assert_eq!(
+            parse_input("noop\nmul 3"),
+            Err(ParseError::new(2, 1, "mul 3", "`noop` or `addx <value>`"))
+        );
+        assert_eq!(
+            parse_input("addx 999\naddx 1"),
+            Err(ParseError::new(
+                2,
+                6,
+                "1",
+                "a number that keeps X from -1000 to 1000"
+            ))
+        );
+        assert_eq!(
+            parse_input("addx 9223372036854775807"),
+            Err(ParseError::new(
+                1,
+                6,
+                "9223372036854775807",
+                "a number that keeps X from -1000 to 1000"
+            ))
+        );
+    }
+}
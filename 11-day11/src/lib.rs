@@ -0,0 +1,374 @@
+use aoc_common::error::parse_token;
+use aoc_common::{Answer, ParseError, Solution};
+use lazy_static::lazy_static;
+use regex::Regex;
+use tracing::{debug, trace};
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Parsed = Vec<Monkey>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_monkeys(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed).into()
+    }
+}
+
+lazy_static! {
+    static ref RE_NUM: Regex = Regex::new(r"(?P<num>\d+)").unwrap();
+    static ref RE_OP: Regex = Regex::new(r".* new = old (?P<op>[+*]) (?P<arg>.+)").unwrap();
+}
+
+#[derive(Clone)]
+pub struct Monkey {
+    items: Vec<u128>,
+    operation: Operation,
+    test_case: u128,
+    test_true: usize,
+    test_false: usize,
+    worry_level_divisor: u128,
+    num_inspections: u128,
+}
+
+impl Monkey {
+    fn new(worry_level_divisor: u128) -> Monkey {
+        Monkey {
+            items: Vec::new(),
+            operation: Operation::Add(0),
+            test_case: 0,
+            test_true: 0,
+            test_false: 0,
+            worry_level_divisor,
+            num_inspections: 0,
+        }
+    }
+    fn add_item(&mut self, item: u128) {
+        self.items.push(item);
+    }
+
+    /// Inspects the last item and picks whom to throw it to, or `None` if its worry level
+    /// outgrows a `u128`.
+    fn process_turn(&mut self) -> Option<(u128, usize)> {
+        let item = self.inspect()? / self.worry_level_divisor;
+        self.num_inspections += 1;
+        if item.is_multiple_of(self.test_case) {
+            Some((item, self.test_true))
+        } else {
+            Some((item, self.test_false))
+        }
+    }
+
+    fn inspect(&mut self) -> Option<u128> {
+        let item = self.items.pop().unwrap();
+        match self.operation {
+            Operation::Add(i) => item.checked_add(i),
+            Operation::Multiply(factor) => item.checked_mul(factor),
+            Operation::AddOld => item.checked_add(item),
+            Operation::MultiplyOld => item.checked_mul(item),
+        }
+    }
+}
+
+#[derive(Clone)]
+enum Operation {
+    Multiply(u128),
+    Add(u128),
+    MultiplyOld,
+    AddOld,
+}
+
+impl Operation {
+    /// The same operation modulo `modulo`.
+    fn reduced(&self, modulo: u128) -> Operation {
+        match *self {
+            Operation::Multiply(factor) => Operation::Multiply(factor % modulo),
+            Operation::Add(i) => Operation::Add(i % modulo),
+            ref op => op.clone(),
+        }
+    }
+}
+
+/// The product of all divisors is bounded so that worry levels below it can be multiplied
+/// without overflowing a `u128`.
+const MAX_MODULO: u128 = u64::MAX as u128;
+
+fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, ParseError> {
+    let num_monkeys = input.split("\n\n").count();
+    let target_expected = format!("a monkey number below {}", num_monkeys);
+    let mut monkeys: Vec<Monkey> = Vec::new();
+    let mut line_no: usize = 1;
+    let mut modulo: u128 = 1;
+    // the line and argument of every monkey's operation, where overflows are reported
+    let mut operations: Vec<(usize, &str, &str)> = Vec::new();
+    for (idx, monk) in input.split("\n\n").enumerate() {
+        let mut c_monkey: Monkey = Monkey::new(1);
+        let mut lines = monk.lines().enumerate().map(|(i, l)| (line_no + i, l));
+        let end_line = line_no + monk.lines().count();
+        // Skip monkey name
+        next_field(&mut lines, end_line, "Monkey ", "`Monkey <n>:`")?;
+        let (items_line_no, line, items) = next_field(
+            &mut lines,
+            end_line,
+            "Starting items:",
+            "`Starting items: <item>, ...`",
+        )?;
+        for item in RE_NUM.find_iter(items) {
+            c_monkey.add_item(parse_token(
+                items_line_no,
+                line,
+                item.as_str(),
+                "a worry level",
+            )?);
+        }
+        // parse operation
+        let (op_line_no, line, _) = next_field(
+            &mut lines,
+            end_line,
+            "Operation:",
+            "`Operation: new = old <+|*> <value>`",
+        )?;
+        let Some(cap) = RE_OP.captures(line) else {
+            return Err(ParseError::at(
+                op_line_no,
+                line,
+                line,
+                "`Operation: new = old <+|*> <value>`",
+            ));
+        };
+        let arg = cap.name("arg").unwrap().as_str();
+        c_monkey.operation = match (&cap["op"], arg) {
+            ("*", "old") => Operation::MultiplyOld,
+            ("+", "old") => Operation::AddOld,
+            ("*", _) => {
+                Operation::Multiply(parse_token(op_line_no, line, arg, "a number or `old`")?)
+            }
+            _ => Operation::Add(parse_token(op_line_no, line, arg, "a number or `old`")?),
+        };
+        operations.push((op_line_no, line, arg));
+        let (test_line_no, line, divisor) = next_field(
+            &mut lines,
+            end_line,
+            "Test: divisible by ",
+            "`Test: divisible by <n>`",
+        )?;
+        c_monkey.test_case = parse_token(test_line_no, line, divisor, "a non-zero divisor")?;
+        if c_monkey.test_case == 0 {
+            return Err(ParseError::at(
+                test_line_no,
+                line,
+                divisor,
+                "a non-zero divisor",
+            ));
+        }
+        modulo = match modulo.checked_mul(c_monkey.test_case) {
+            Some(m) if m <= MAX_MODULO => m,
+            _ => {
+                return Err(ParseError::at(
+                    test_line_no,
+                    line,
+                    divisor,
+                    &format!(
+                        "a divisor that keeps the product of all divisors within {}",
+                        MAX_MODULO
+                    ),
+                ))
+            }
+        };
+        for if_true in [true, false] {
+            let (prefix, expected) = if if_true {
+                (
+                    "If true: throw to monkey ",
+                    "`If true: throw to monkey <n>`",
+                )
+            } else {
+                (
+                    "If false: throw to monkey ",
+                    "`If false: throw to monkey <n>`",
+                )
+            };
+            let (target_line_no, line, target) =
+                next_field(&mut lines, end_line, prefix, expected)?;
+            let target: usize = parse_token(target_line_no, line, target, &target_expected)?;
+            if target >= num_monkeys || target == idx {
+                return Err(ParseError::at(
+                    target_line_no,
+                    line,
+                    line.trim_start().strip_prefix(prefix).unwrap(),
+                    &format!("{} other than {}", target_expected, idx),
+                ));
+            }
+            if if_true {
+                c_monkey.test_true = target;
+            } else {
+                c_monkey.test_false = target;
+            }
+        }
+        monkeys.push(c_monkey);
+        // skip the blank line between two monkeys
+        line_no = end_line + 1;
+    }
+    // part 1 can't keep worry levels small, so play it here once, without tracing, to make
+    // sure they fit
+    let played =
+        tracing::subscriber::with_default(tracing::subscriber::NoSubscriber::default(), || {
+            keep_away(&monkeys, 20, 3, None)
+        });
+    if let Err(idx) = played {
+        let (op_line_no, line, arg) = operations[idx];
+        return Err(ParseError::at(
+            op_line_no,
+            line,
+            arg,
+            "an operation that keeps worry levels within 128 bits for 20 rounds",
+        ));
+    }
+    Ok(monkeys)
+}
+
+/// Takes the next line of a monkey description and checks that it starts with `prefix`,
+/// returning the line number, the whole line and the text after the prefix.
+fn next_field<'a>(
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+    end_line: usize,
+    prefix: &str,
+    expected: &str,
+) -> Result<(usize, &'a str, &'a str), ParseError> {
+    let Some((line_no, line)) = lines.next() else {
+        return Err(ParseError::end_of_input(end_line, expected));
+    };
+    match line.trim_start().strip_prefix(prefix) {
+        Some(rest) => Ok((line_no, line, rest)),
+        None => Err(ParseError::at(line_no, line, line.trim_start(), expected)),
+    }
+}
+
+/// How many items every monkey has inspected so far.
+fn inspections(monkeys: &[Monkey]) -> Vec<u128> {
+    monkeys.iter().map(|m| m.num_inspections).collect()
+}
+
+/// Plays `rounds` rounds, dividing worry levels by `relief` after every inspection and, with a
+/// `modulo`, keeping them below it. Returns the monkey business, or the monkey whose operation
+/// made a worry level overflow.
+fn keep_away(
+    input: &[Monkey],
+    rounds: usize,
+    relief: u128,
+    modulo: Option<u128>,
+) -> Result<u128, usize> {
+    let mut monkeys: Vec<Monkey> = input.to_vec();
+    for monkey in monkeys.iter_mut() {
+        monkey.worry_level_divisor = relief;
+        if let Some(modulo) = modulo {
+            monkey.operation = monkey.operation.reduced(modulo);
+            for item in monkey.items.iter_mut() {
+                *item %= modulo;
+            }
+        }
+    }
+    for round in 1..=rounds {
+        for i in 0..monkeys.len() {
+            while !monkeys[i].items.is_empty() {
+                let Some((mut item, target)) = monkeys[i].process_turn() else {
+                    return Err(i);
+                };
+                if let Some(modulo) = modulo {
+                    item %= modulo;
+                }
+                trace!(round, monkey = i, item, target, "threw");
+                monkeys[target].add_item(item);
+            }
+        }
+        debug!(round, inspections = ?inspections(&monkeys), "round done");
+    }
+    let mut inspections: Vec<u128> = inspections(&monkeys);
+    inspections.sort_by(|a, b| b.cmp(a));
+    Ok(inspections[0] * inspections[1])
+}
+
+#[allow(clippy::ptr_arg)]
+fn part1(input: &Vec<Monkey>) -> u128 {
+    keep_away(input, 20, 3, None).expect("the parser makes sure worry levels fit")
+}
+
+#[allow(clippy::ptr_arg)]
+fn part2(input: &Vec<Monkey>) -> u128 {
+    let modulo: u128 = input.iter().map(|x| x.test_case).product();
+    // below the product of all divisors, which the parser bounds, nothing overflows
+    keep_away(input, 10_000, 1, Some(modulo)).expect("worry levels stay below the modulo")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::input;
+
+    #[test]
+    fn test_part1() {
+        let input = parse_monkeys(&input::read_input("sample").unwrap()).unwrap();
+        assert_eq!(part1(&input), 10605);
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_monkeys(&input::read_input("sample").unwrap()).unwrap();
+        assert_eq!(part2(&input), 2713310158);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let sample = input::read_input("sample").unwrap();
+        let broken = sample.replace("Test: divisible by 13", "Test: divisible by 0");
+        assert_eq!(
+            parse_monkeys(&broken).err(),
+            Some(ParseError::new(18, 22, "0", "a non-zero divisor"))
+        );
+        let broken = sample.replace("divisible by 13", "divisible by 18446744073709551615");
+        assert_eq!(
+            parse_monkeys(&broken).err(),
+            Some(ParseError::new(
+                18,
+                22,
+                "18446744073709551615",
+                "a divisor that keeps the product of all divisors within 18446744073709551615"
+            ))
+        );
+        let broken = sample.replace("old + 6", "old + 340282366920938463463374607431768211455");
+        assert_eq!(
+            parse_monkeys(&broken).err(),
+            Some(ParseError::new(
+                10,
+                26,
+                "340282366920938463463374607431768211455",
+                "an operation that keeps worry levels within 128 bits for 20 rounds"
+            ))
+        );
+        let broken = sample.replace("throw to monkey 3", "throw to monkey 7");
+        assert_eq!(
+            parse_monkeys(&broken).err(),
+            Some(ParseError::new(
+                6,
+                31,
+                "7",
+                "a monkey number below 4 other than 0"
+            ))
+        );
+        let truncated = &sample[..sample.rfind("    If false").unwrap()];
+        assert_eq!(
+            parse_monkeys(truncated).err(),
+            Some(ParseError::end_of_input(
+                27,
+                "`If false: throw to monkey <n>`"
+            ))
+        );
+    }
+}
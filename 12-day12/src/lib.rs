@@ -0,0 +1,320 @@
+use aoc_common::image::{self, Image};
+use aoc_common::visualize::{self, Colour, Glyph, Visualize};
+use aoc_common::{Answer, Coordinate, Grid, ParseError, Solution};
+use std::collections::VecDeque;
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    const DAY: u8 = 12;
+    type Parsed = (Grid<isize>, Coordinate, Coordinate);
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        gen_map(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(&parsed.0, &parsed.1, &parsed.2).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(&parsed.0, &parsed.2).into()
+    }
+}
+
+#[allow(clippy::needless_return)]
+fn gen_map(input: &str) -> Result<(Grid<isize>, Coordinate, Coordinate), ParseError> {
+    let chars: Grid<char> = Grid::parse(input, "an elevation `a`-`z`, `S` or `E`", |ch| {
+        (ch.is_ascii_lowercase() || ch == 'S' || ch == 'E').then_some(ch)
+    })?;
+    let start = find_single(&chars, 'S', "start")?;
+    let goal = find_single(&chars, 'E', "goal")?;
+    let map: Grid<isize> = chars.map(|&ch| match ch {
+        'S' => 'a' as isize - 97,
+        'E' => 'z' as isize - 97,
+        _ => ch as isize - 97,
+    });
+    if !reaches(&map, start, goal) {
+        return Err(ParseError::new(
+            goal.y as usize + 1,
+            goal.x as usize + 1,
+            "E",
+            "a goal `E` that can be reached from the start `S`",
+        ));
+    }
+    return Ok((map, start, goal));
+}
+
+/// Whether climbing at most one step up at a time leads from `start` to `goal`.
+fn reaches(map: &Grid<isize>, start: Coordinate, goal: Coordinate) -> bool {
+    let mut explored: Grid<bool> = map.map(|_| false);
+    explored[start] = true;
+    let mut stack: Vec<Coordinate> = vec![start];
+    while let Some(c) = stack.pop() {
+        if c == goal {
+            return true;
+        }
+        for n in map.neighbours4(c) {
+            if !explored[n] && map[n] - map[c] <= 1 {
+                explored[n] = true;
+                stack.push(n);
+            }
+        }
+    }
+    false
+}
+
+/// The position of the one cell marked `marker`, described as `name` in errors.
+fn find_single(chars: &Grid<char>, marker: char, name: &str) -> Result<Coordinate, ParseError> {
+    let mut found = chars.iter().filter(|(_, &ch)| ch == marker).map(|(c, _)| c);
+    let Some(first) = found.next() else {
+        return Err(ParseError::end_of_input(
+            chars.height() + 1,
+            &format!("a {name} `{marker}`"),
+        ));
+    };
+    if let Some(second) = found.next() {
+        return Err(ParseError::new(
+            second.y as usize + 1,
+            second.x as usize + 1,
+            &marker.to_string(),
+            &format!("a single {name} `{marker}`"),
+        ));
+    }
+    Ok(first)
+}
+
+/// A breadth first search over the heightmap as far as it got: the elevation letters, green
+/// where the search has been, with the start and the goal in red.
+pub struct Search<'a> {
+    pub map: &'a Grid<isize>,
+    pub explored: &'a Grid<bool>,
+    pub start: Coordinate,
+    pub goal: Coordinate,
+    pub steps: usize,
+}
+
+impl Visualize for Search<'_> {
+    fn bounds(&self) -> (Coordinate, Coordinate) {
+        (
+            Coordinate::new(0, 0),
+            Coordinate::new(
+                self.map.width() as isize - 1,
+                self.map.height() as isize - 1,
+            ),
+        )
+    }
+
+    fn glyph(&self, c: Coordinate) -> Glyph {
+        if c == self.start {
+            return Glyph::new('S', Colour::Red);
+        }
+        if c == self.goal {
+            return Glyph::new('E', Colour::Red);
+        }
+        let letter = (b'a' + self.map[c] as u8) as char;
+        if self.explored[c] {
+            Glyph::new(letter, Colour::Green)
+        } else {
+            Glyph::new(letter, Colour::Grey)
+        }
+    }
+
+    fn focus(&self) -> Option<Coordinate> {
+        Some(self.goal)
+    }
+
+    fn caption(&self) -> String {
+        format!("{} steps from the start", self.steps)
+    }
+}
+
+fn part1(map: &Grid<isize>, start: &Coordinate, goal: &Coordinate) -> usize {
+    let mut q: VecDeque<(Coordinate, usize)> = VecDeque::from([(*start, 0)]);
+    let mut explored: Grid<bool> = map.map(|_| false);
+    explored[*start] = true;
+    // where each position was first reached from, to draw the path
+    let mut previous: Grid<Option<Coordinate>> = map.map(|_| None);
+    let mut layer: usize = 0;
+    while let Some((c, cost)) = q.pop_front() {
+        // everything up to `layer` steps away has been found once the next layer starts
+        if cost > layer || c == *goal {
+            layer = cost;
+            visualize::frame(&Search {
+                map,
+                explored: &explored,
+                start: *start,
+                goal: *goal,
+                steps: layer,
+            });
+        }
+        if c == *goal {
+            image::save("path", || path_image(map, &path_to(&previous, c)));
+            return cost;
+        }
+        for n in map.neighbours4(c) {
+            if !explored[n] && map[n] - map[c] <= 1 {
+                q.push_back((n, cost + 1));
+                explored[n] = true;
+                previous[n] = Some(c);
+            }
+        }
+    }
+    unreachable!("the parser makes sure the start reaches the goal");
+}
+
+/// The positions from the start of a search to `end`, following `previous` back.
+fn path_to(previous: &Grid<Option<Coordinate>>, end: Coordinate) -> Vec<Coordinate> {
+    let mut path: Vec<Coordinate> = vec![end];
+    while let Some(c) = previous[*path.last().unwrap()] {
+        path.push(c);
+    }
+    path.reverse();
+    path
+}
+
+/// The heightmap from dark blue (`a`) to pale yellow (`z`) with `path` in red.
+fn path_image(map: &Grid<isize>, path: &[Coordinate]) -> Image {
+    let mut on_path: Grid<bool> = map.map(|_| false);
+    for &c in path {
+        on_path[c] = true;
+    }
+    Image::from_fn(map.width(), map.height(), |c| {
+        if on_path[c] {
+            [220, 50, 47]
+        } else {
+            image::gradient(map[c] as f64 / 25.0)
+        }
+    })
+    .scaled(4)
+}
+
+#[allow(clippy::needless_return)]
+fn part2(map: &Grid<isize>, goal: &Coordinate) -> usize {
+    let mut path_costs: Vec<usize> = Vec::new();
+    for (s, h) in map.iter() {
+        if *h != 'a' as isize - 97 {
+            continue;
+        }
+        let mut q: VecDeque<(Coordinate, usize)> = VecDeque::from([(s, 0)]);
+        let mut explored: Grid<bool> = map.map(|_| false);
+        explored[s] = true;
+        while let Some((c, cost)) = q.pop_front() {
+            if c == *goal {
+                path_costs.push(cost);
+                break;
+            }
+            for n in map.neighbours4(c) {
+                if !explored[n] && map[n] - map[c] <= 1 {
+                    q.push_back((n, cost + 1));
+                    explored[n] = true;
+                }
+            }
+        }
+    }
+    // the start is at elevation `a` too, so there is at least one path
+    return *path_costs
+        .iter()
+        .min()
+        .expect("the parser makes sure the start reaches the goal");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::input;
+
+    #[test]
+    fn test_part1() {
+        let (map, start, goal): (Grid<isize>, Coordinate, Coordinate) =
+            gen_map(&input::read_input("sample").unwrap()).unwrap();
+        assert_eq!(part1(&map, &start, &goal), 31);
+    }
+
+    #[test]
+    fn test_visualize() {
+        let (map, start, goal): (Grid<isize>, Coordinate, Coordinate) =
+            gen_map(&input::read_input("sample").unwrap()).unwrap();
+        let mut explored: Grid<bool> = map.map(|_| false);
+        explored[start] = true;
+        explored[Coordinate::new(1, 0)] = true;
+        let search = Search {
+            map: &map,
+            explored: &explored,
+            start,
+            goal,
+            steps: 1,
+        };
+        let (from, to) = search.bounds();
+        assert_eq!(
+            visualize::picture(&search, from, to, false),
+            "\
+Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi
+"
+        );
+        assert_eq!(
+            search.glyph(Coordinate::new(1, 0)),
+            Glyph::new('a', Colour::Green)
+        );
+        assert_eq!(
+            search.glyph(Coordinate::new(2, 0)),
+            Glyph::new('b', Colour::Grey)
+        );
+    }
+
+    #[test]
+    fn test_path_image() {
+        let (map, _start, _goal): (Grid<isize>, Coordinate, Coordinate) =
+            gen_map(&input::read_input("sample").unwrap()).unwrap();
+        let mut previous: Grid<Option<Coordinate>> = map.map(|_| None);
+        previous[Coordinate::new(1, 0)] = Some(Coordinate::new(0, 0));
+        previous[Coordinate::new(1, 1)] = Some(Coordinate::new(1, 0));
+        let path = path_to(&previous, Coordinate::new(1, 0));
+        assert_eq!(path, vec![Coordinate::new(0, 0), Coordinate::new(1, 0)]);
+        let image = path_image(&map, &path);
+        assert_eq!((image.width(), image.height()), (32, 20));
+        assert_eq!(image.get(5, 3), Some([220, 50, 47]));
+        assert_eq!(image.get(9, 0), Some(image::gradient(1.0 / 25.0)));
+    }
+
+    #[test]
+    fn test_part2() {
+        let (map, _start, goal): (Grid<isize>, Coordinate, Coordinate) =
+            gen_map(&input::read_input("sample").unwrap()).unwrap();
+        assert_eq!(part2(&map, &goal), 29);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            gen_map("Sab\nc1E").err(),
+            Some(ParseError::new(
+                2,
+                2,
+                "1",
+                "an elevation `a`-`z`, `S` or `E`"
+            ))
+        );
+        assert_eq!(
+            gen_map("Sab\ncde").err(),
+            Some(ParseError::end_of_input(3, "a goal `E`"))
+        );
+        assert_eq!(
+            gen_map("SaE\ncdS").err(),
+            Some(ParseError::new(2, 3, "S", "a single start `S`"))
+        );
+        assert_eq!(
+            gen_map("SzE").err(),
+            Some(ParseError::new(
+                1,
+                3,
+                "E",
+                "a goal `E` that can be reached from the start `S`"
+            ))
+        );
+    }
+}
@@ -0,0 +1,463 @@
+use aoc_common::error::parse_token;
+use aoc_common::image::{self, Image};
+use aoc_common::visualize::{self, Colour, Glyph, Visualize};
+use aoc_common::{Answer, Coordinate, Grid, ParseError, Solution};
+use tracing::debug;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Parsed = Cave;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_input(input)
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Answer {
+        part1(parsed).into()
+    }
+
+    fn part2(parsed: &Self::Parsed) -> Answer {
+        part2(parsed).into()
+    }
+}
+
+#[derive(Eq, PartialEq, Clone, Copy)]
+pub enum Content {
+    Air,
+    Rock,
+    Sand,
+}
+
+/// The slice of the cave that sand can reach: rock and settled sand on a grid wide enough to
+/// hold the pile that builds up on the floor in part 2.
+#[derive(Clone)]
+pub struct Cave {
+    grid: Grid<Content>,
+    /// Cave x coordinate of the grid's first column.
+    x_offset: isize,
+    /// Depth of the lowest rock.
+    floor_level: isize,
+}
+
+impl Cave {
+    fn new(rocks: &[Coordinate]) -> Cave {
+        let floor_level = rocks.iter().map(|c| c.y).max().unwrap_or(0);
+        // Sand spreads at most one column per row, so the part 2 pile is a triangle resting
+        // on the floor two rows below the lowest rock.
+        let spread = floor_level + 2;
+        let x_min = rocks
+            .iter()
+            .map(|c| c.x)
+            .min()
+            .unwrap_or(500)
+            .min(500 - spread)
+            - 1;
+        let x_max = rocks
+            .iter()
+            .map(|c| c.x)
+            .max()
+            .unwrap_or(500)
+            .max(500 + spread)
+            + 1;
+        let mut cave = Cave {
+            grid: Grid::new(
+                (x_max - x_min + 1) as usize,
+                (floor_level + 3) as usize,
+                Content::Air,
+            ),
+            x_offset: x_min,
+            floor_level,
+        };
+        for rock in rocks {
+            cave.set(*rock, Content::Rock);
+        }
+        cave
+    }
+
+    fn to_grid(&self, c: Coordinate) -> Coordinate {
+        Coordinate {
+            x: c.x - self.x_offset,
+            y: c.y,
+        }
+    }
+
+    /// What fills `c`; everything outside the grid is air.
+    fn get(&self, c: Coordinate) -> Content {
+        self.grid
+            .get(self.to_grid(c))
+            .copied()
+            .unwrap_or(Content::Air)
+    }
+
+    fn set(&mut self, c: Coordinate, content: Content) {
+        let c = self.to_grid(c);
+        self.grid[c] = content;
+    }
+}
+
+/// Where the sand comes from.
+const SOURCE: Coordinate = Coordinate { x: 500, y: 0 };
+
+impl Visualize for Cave {
+    fn bounds(&self) -> (Coordinate, Coordinate) {
+        (
+            Coordinate::new(self.x_offset, 0),
+            Coordinate::new(
+                self.x_offset + self.grid.width() as isize - 1,
+                self.grid.height() as isize - 1,
+            ),
+        )
+    }
+
+    fn glyph(&self, c: Coordinate) -> Glyph {
+        match self.get(c) {
+            Content::Air if c == SOURCE => Glyph::new('+', Colour::Red),
+            Content::Air => Glyph::new('.', Colour::Grey),
+            Content::Rock => Glyph::new('#', Colour::Default),
+            Content::Sand => Glyph::new('o', Colour::Yellow),
+        }
+    }
+
+    fn focus(&self) -> Option<Coordinate> {
+        Some(SOURCE)
+    }
+
+    fn caption(&self) -> String {
+        let grains = self
+            .grid
+            .iter()
+            .filter(|(_, &c)| c == Content::Sand)
+            .count();
+        format!("{} units of sand", grains)
+    }
+}
+
+/// Coordinates are bounded so that the cave grid stays small whatever the input says; real
+/// scans stay well below this.
+const MAX_COORDINATE: usize = 1_000;
+
+fn parse_coordinate(
+    line_no: usize,
+    line: &str,
+    token: &str,
+    expected: &str,
+) -> Result<isize, ParseError> {
+    let expected = format!("{} from 0 to {}", expected, MAX_COORDINATE);
+    let value: usize = parse_token(line_no, line, token, &expected)?;
+    if value > MAX_COORDINATE {
+        return Err(ParseError::at(line_no, line, token, &expected));
+    }
+    Ok(value as isize)
+}
+
+fn parse_input(input: &str) -> Result<Cave, ParseError> {
+    let mut rocks: Vec<Coordinate> = Vec::new();
+    for (idx, line) in input.lines().enumerate() {
+        let mut prev_coord: Option<Coordinate> = None;
+        for cs in line.split(" -> ") {
+            let Some((x, y)) = cs.split_once(',') else {
+                return Err(ParseError::at(idx + 1, line, cs, "a coordinate `<x>,<y>`"));
+            };
+            let coord: Coordinate = Coordinate {
+                x: parse_coordinate(idx + 1, line, x, "an x coordinate")?,
+                y: parse_coordinate(idx + 1, line, y, "a y coordinate")?,
+            };
+            if let Some(pc) = prev_coord {
+                match pc.delta(coord) {
+                    Coordinate { x: 0, y: dy } => {
+                        for i in 0..dy.abs() + 1 {
+                            rocks.push(Coordinate {
+                                x: coord.x,
+                                y: coord.y + i * dy.signum(),
+                            });
+                        }
+                    }
+                    Coordinate { x: dx, y: 0 } => {
+                        for i in 0..dx.abs() + 1 {
+                            rocks.push(Coordinate {
+                                x: coord.x + i * dx.signum(),
+                                y: coord.y,
+                            });
+                        }
+                    }
+                    Coordinate { .. } => {
+                        return Err(ParseError::at(
+                            idx + 1,
+                            line,
+                            cs,
+                            &format!("a point in a straight line from {},{}", pc.x, pc.y),
+                        ))
+                    }
+                }
+            }
+            prev_coord = Some(coord);
+        }
+    }
+    Ok(Cave::new(&rocks))
+}
+
+fn part1(input: &Cave) -> isize {
+    let mut c_grains: isize = 0;
+    let origin: Coordinate = SOURCE;
+    let mut map = input.clone();
+    let floor_level = map.floor_level;
+    let mut curr_pos = origin;
+    while let Some(dir) = find_fall_direction(curr_pos, &map, floor_level) {
+        match dir {
+            Direction::Down => {
+                curr_pos.y += 1;
+            }
+            Direction::DownLeft => {
+                curr_pos.x -= 1;
+                curr_pos.y += 1;
+            }
+            Direction::DownRight => {
+                curr_pos.x += 1;
+                curr_pos.y += 1;
+            }
+            Direction::Stop => {
+                map.set(curr_pos, Content::Sand);
+                c_grains += 1;
+                debug!(grain = c_grains, x = curr_pos.x, y = curr_pos.y, "settled");
+                visualize::frame(&map);
+                // a cup of rock can fill up all the way to the source
+                if curr_pos == origin {
+                    break;
+                }
+                curr_pos = origin;
+            }
+            Direction::Outside => {
+                break;
+            }
+        }
+    }
+    image::save("cave", || Image::from_glyphs(&map).scaled(4));
+    c_grains
+}
+
+enum Direction {
+    Down,
+    DownRight,
+    DownLeft,
+    Stop,
+    Outside,
+}
+
+fn find_fall_direction(curr_pos: Coordinate, map: &Cave, floor_level: isize) -> Option<Direction> {
+    if curr_pos.y > floor_level {
+        return Some(Direction::Outside);
+    }
+    if map.get(Coordinate {
+        x: curr_pos.x,
+        y: curr_pos.y + 1,
+    }) == Content::Air
+    {
+        return Some(Direction::Down);
+    } else if map.get(Coordinate {
+        x: curr_pos.x - 1,
+        y: curr_pos.y + 1,
+    }) == Content::Air
+    {
+        return Some(Direction::DownLeft);
+    } else if map.get(Coordinate {
+        x: curr_pos.x + 1,
+        y: curr_pos.y + 1,
+    }) == Content::Air
+    {
+        return Some(Direction::DownRight);
+    } else if map.get(Coordinate {
+        x: curr_pos.x,
+        y: curr_pos.y + 1,
+    }) != Content::Air
+        || curr_pos.y + 1 == floor_level
+    {
+        return Some(Direction::Stop);
+    }
+    None
+}
+
+fn part2(input: &Cave) -> isize {
+    let mut c_grains: isize = 0;
+    let origin: Coordinate = SOURCE;
+    let mut map = input.clone();
+    let floor_level = map.floor_level + 2;
+    let mut curr_pos = origin;
+    while let Some(dir) = find_fall_direction2(curr_pos, &map, floor_level) {
+        match dir {
+            Direction::Down => {
+                curr_pos.y += 1;
+            }
+            Direction::DownLeft => {
+                curr_pos.x -= 1;
+                curr_pos.y += 1;
+            }
+            Direction::DownRight => {
+                curr_pos.x += 1;
+                curr_pos.y += 1;
+            }
+            Direction::Stop => {
+                map.set(curr_pos, Content::Sand);
+                c_grains += 1;
+                debug!(grain = c_grains, x = curr_pos.x, y = curr_pos.y, "settled");
+                visualize::frame(&map);
+                // the source is blocked once it is buried
+                if curr_pos == origin {
+                    break;
+                }
+                curr_pos = origin;
+            }
+            Direction::Outside => {
+                break;
+            }
+        }
+    }
+    image::save("cave", || Image::from_glyphs(&map).scaled(4));
+    c_grains
+}
+
+fn find_fall_direction2(curr_pos: Coordinate, map: &Cave, floor_level: isize) -> Option<Direction> {
+    if curr_pos.y + 1 == floor_level {
+        return Some(Direction::Stop);
+    }
+    if map.get(Coordinate {
+        x: curr_pos.x,
+        y: curr_pos.y + 1,
+    }) == Content::Air
+    {
+        return Some(Direction::Down);
+    } else if map.get(Coordinate {
+        x: curr_pos.x - 1,
+        y: curr_pos.y + 1,
+    }) == Content::Air
+    {
+        return Some(Direction::DownLeft);
+    } else if map.get(Coordinate {
+        x: curr_pos.x + 1,
+        y: curr_pos.y + 1,
+    }) == Content::Air
+    {
+        return Some(Direction::DownRight);
+    } else if map.get(Coordinate {
+        x: curr_pos.x,
+        y: curr_pos.y + 1,
+    }) != Content::Air
+        || curr_pos.y + 1 == floor_level
+    {
+        return Some(Direction::Stop);
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::input;
+    use proptest::prelude::*;
+
+    /// Rock paths of alternating horizontal and vertical segments below the sand source.
+    fn paths() -> impl Strategy<Value = String> {
+        let path = (
+            (470..530isize, 1..20isize),
+            prop::collection::vec(-6..=6isize, 1..6),
+        )
+            .prop_map(|((x, y), segments)| {
+                let mut point = Coordinate { x, y };
+                let mut points = vec![format!("{},{}", x, y)];
+                for (i, len) in segments.into_iter().enumerate() {
+                    if i % 2 == 0 {
+                        point.x += len;
+                    } else {
+                        point.y = (point.y + len).max(1);
+                    }
+                    points.push(format!("{},{}", point.x, point.y));
+                }
+                points.join(" -> ")
+            });
+        prop::collection::vec(path, 1..6).prop_map(|paths| paths.join("\n"))
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn prop_floor_holds_more_sand(input in paths()) {
+            let cave = parse_input(&input).unwrap();
+            let (part1, part2) = (part1(&cave), part2(&cave));
+            prop_assert!(part2 >= part1, "{} < {} for\n{}", part2, part1, input);
+            // The pile on the floor is at most a full triangle under the source.
+            prop_assert!(part2 <= (cave.floor_level + 2) * (cave.floor_level + 2));
+        }
+    }
+
+    #[test]
+    fn test_part1() {
+        let input = parse_input(&input::read_input("sample").unwrap()).unwrap();
+        assert_eq!(part1(&input), 24);
+    }
+
+    #[test]
+    fn test_visualize() {
+        let cave = parse_input(&input::read_input("sample").unwrap()).unwrap();
+        assert_eq!(
+            visualize::picture(
+                &cave,
+                Coordinate::new(494, 0),
+                Coordinate::new(503, 9),
+                false
+            ),
+            "\
+......+...
+..........
+..........
+..........
+....#...##
+....#...#.
+..###...#.
+........#.
+........#.
+#########.
+"
+        );
+        assert_eq!(cave.caption(), "0 units of sand");
+    }
+
+    #[test]
+    fn test_part2() {
+        let input = parse_input(&input::read_input("sample").unwrap()).unwrap();
+        assert_eq!(part2(&input), 93);
+    }
+
+    #[test]
+    fn test_part1_fills_up_to_the_source() {
+        let input = parse_input("498,0 -> 498,2 -> 502,2 -> 502,0").unwrap();
+        assert_eq!(part1(&input), 4);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_input("498,4 -> 498,6 -> 496,8").err(),
+            Some(ParseError::new(
+                1,
+                19,
+                "496,8",
+                "a point in a straight line from 498,6"
+            ))
+        );
+        assert_eq!(
+            parse_input("498,4 -> 498;6").err(),
+            Some(ParseError::new(1, 10, "498;6", "a coordinate `<x>,<y>`"))
+        );
+        assert_eq!(
+            parse_input("498,4 -> 4294967296,4").err(),
+            Some(ParseError::new(
+                1,
+                10,
+                "4294967296",
+                "an x coordinate from 0 to 1000"
+            ))
+        );
+    }
+}
@@ -0,0 +1,49 @@
+use std::ops::{Add, Sub};
+
+/// A position on a 2D map. Defaults to signed components, which is what most of the
+/// simulations need, but days that only index into a grid can use `Coordinate<usize>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Coordinate<T = isize> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Coordinate<T> {
+    pub fn new(x: T, y: T) -> Coordinate<T> {
+        Coordinate { x, y }
+    }
+}
+
+impl<T: Sub<Output = T>> Coordinate<T> {
+    /// Component-wise difference `self - other`.
+    pub fn delta(self, other: Coordinate<T>) -> Coordinate<T> {
+        Coordinate {
+            x: self.x - other.x,
+            y: self.y - other.y,
+        }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Coordinate<T> {
+    type Output = Coordinate<T>;
+
+    fn add(self, other: Coordinate<T>) -> Coordinate<T> {
+        Coordinate {
+            x: self.x + other.x,
+            y: self.y + other.y,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delta() {
+        let a: Coordinate = Coordinate::new(3, 7);
+        let b: Coordinate = Coordinate::new(5, 2);
+        assert_eq!(a.delta(b), Coordinate::new(-2, 5));
+        assert_eq!(a + b, Coordinate::new(8, 9));
+    }
+}
@@ -0,0 +1,111 @@
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// A malformed puzzle input: where it went wrong, what was found there and what the
+/// parser expected instead. Lines and columns are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub expected: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, column: usize, text: &str, expected: &str) -> ParseError {
+        ParseError {
+            line,
+            column,
+            text: text.to_string(),
+            expected: expected.to_string(),
+        }
+    }
+
+    /// An error for `token`, which has to be a slice of `line`; the column is derived from
+    /// where the token sits inside the line.
+    pub fn at(line_no: usize, line: &str, token: &str, expected: &str) -> ParseError {
+        ParseError::new(line_no, column_of(line, token), token, expected)
+    }
+
+    /// An error for input that ended while the parser still expected more, e.g. a missing
+    /// section. `line` is the line after the last one that was read.
+    pub fn end_of_input(line: usize, expected: &str) -> ParseError {
+        ParseError::new(line, 1, "", expected)
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.text.is_empty() {
+            write!(f, "end of input")
+        } else {
+            write!(f, "`{}`", self.text)
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Parses `token`, a slice of `line`, into a number or any other `FromStr` type.
+pub fn parse_token<T: FromStr>(
+    line_no: usize,
+    line: &str,
+    token: &str,
+    expected: &str,
+) -> Result<T, ParseError> {
+    token
+        .parse::<T>()
+        .map_err(|_| ParseError::at(line_no, line, token, expected))
+}
+
+/// 1-based column of `token` inside `line`, or 1 if the token is not part of the line.
+fn column_of(line: &str, token: &str) -> usize {
+    let start = line.as_ptr() as usize;
+    let pos = token.as_ptr() as usize;
+    if pos >= start && pos + token.len() <= start + line.len() {
+        line[..pos - start].chars().count() + 1
+    } else {
+        1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_column() {
+        let line = "move 1 from x to 3";
+        let token = line.split(' ').nth(3).unwrap();
+        let err = ParseError::at(4, line, token, "a stack number");
+        assert_eq!(err, ParseError::new(4, 13, "x", "a stack number"));
+        assert_eq!(
+            err.to_string(),
+            "line 4, column 13: expected a stack number, found `x`"
+        );
+    }
+
+    #[test]
+    fn test_parse_token() {
+        let line = "R 12";
+        assert_eq!(parse_token::<isize>(1, line, &line[2..], "steps"), Ok(12));
+        assert_eq!(
+            parse_token::<isize>(1, line, &line[..1], "steps"),
+            Err(ParseError::new(1, 1, "R", "steps"))
+        );
+    }
+
+    #[test]
+    fn test_end_of_input() {
+        assert_eq!(
+            ParseError::end_of_input(3, "a list of moves").to_string(),
+            "line 3, column 1: expected a list of moves, found end of input"
+        );
+    }
+}
@@ -0,0 +1,318 @@
+use crate::error::ParseError;
+use crate::Coordinate;
+use std::ops::{Index, IndexMut};
+
+/// Unit steps in the four orthogonal directions: up, right, down, left.
+pub const DIRECTIONS4: [Coordinate; 4] = [
+    Coordinate { x: 0, y: -1 },
+    Coordinate { x: 1, y: 0 },
+    Coordinate { x: 0, y: 1 },
+    Coordinate { x: -1, y: 0 },
+];
+
+/// Unit steps to all eight surrounding cells, row by row from the top left.
+pub const DIRECTIONS8: [Coordinate; 8] = [
+    Coordinate { x: -1, y: -1 },
+    Coordinate { x: 0, y: -1 },
+    Coordinate { x: 1, y: -1 },
+    Coordinate { x: -1, y: 0 },
+    Coordinate { x: 1, y: 0 },
+    Coordinate { x: -1, y: 1 },
+    Coordinate { x: 0, y: 1 },
+    Coordinate { x: 1, y: 1 },
+];
+
+/// A dense, rectangular 2D map stored row by row. Positions are signed so that stepping off
+/// an edge simply yields `None` from the checked accessors instead of wrapping around.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// A `width` x `height` grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Builds a grid from cells given row by row. Panics if they don't fill whole rows.
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(
+            width > 0 && cells.len().is_multiple_of(width),
+            "{} cells don't make rows of {}",
+            cells.len(),
+            width
+        );
+        Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    /// Parses a character map, one row per line. `cell` converts a character and returns `None`
+    /// for characters that aren't allowed, which is reported with `expected`.
+    pub fn parse<F>(input: &str, expected: &str, mut cell: F) -> Result<Grid<T>, ParseError>
+    where
+        F: FnMut(char) -> Option<T>,
+    {
+        let mut width: Option<usize> = None;
+        let mut cells: Vec<T> = Vec::new();
+        for (y, line) in input.lines().enumerate() {
+            let mut row_len: usize = 0;
+            for (x, ch) in line.chars().enumerate() {
+                match cell(ch) {
+                    Some(c) => cells.push(c),
+                    None => return Err(ParseError::new(y + 1, x + 1, &ch.to_string(), expected)),
+                }
+                row_len += 1;
+            }
+            match width {
+                None if row_len == 0 => {
+                    return Err(ParseError::end_of_input(y + 1, "a row of the map"))
+                }
+                None => width = Some(row_len),
+                Some(w) if w != row_len => {
+                    return Err(ParseError::at(
+                        y + 1,
+                        line,
+                        line,
+                        &format!("a row of {} cells like the ones before", w),
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+        match width {
+            Some(w) => Ok(Grid::from_vec(w, cells)),
+            None => Err(ParseError::end_of_input(1, "a row of the map")),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, c: Coordinate) -> bool {
+        c.x >= 0 && c.y >= 0 && (c.x as usize) < self.width && (c.y as usize) < self.height
+    }
+
+    fn offset(&self, c: Coordinate) -> Option<usize> {
+        if self.contains(c) {
+            Some(c.y as usize * self.width + c.x as usize)
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, c: Coordinate) -> Option<&T> {
+        self.offset(c).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, c: Coordinate) -> Option<&mut T> {
+        self.offset(c).map(|i| &mut self.cells[i])
+    }
+
+    /// Every position, row by row from the top left.
+    pub fn coordinates(&self) -> impl Iterator<Item = Coordinate> {
+        let (width, height) = (self.width as isize, self.height as isize);
+        (0..height).flat_map(move |y| (0..width).map(move |x| Coordinate { x, y }))
+    }
+
+    /// Every cell with its position, row by row from the top left.
+    pub fn iter(&self) -> impl Iterator<Item = (Coordinate, &T)> {
+        self.coordinates().zip(self.cells.iter())
+    }
+
+    /// The position of the first cell, row by row, that satisfies `pred`.
+    pub fn position<P: FnMut(&T) -> bool>(&self, mut pred: P) -> Option<Coordinate> {
+        self.iter().find(|(_, v)| pred(v)).map(|(c, _)| c)
+    }
+
+    pub fn map<U, F: FnMut(&T) -> U>(&self, f: F) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    /// The in-bounds positions orthogonally next to `c`.
+    pub fn neighbours4(&self, c: Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        DIRECTIONS4
+            .iter()
+            .map(move |&d| c + d)
+            .filter(|&n| self.contains(n))
+    }
+
+    /// The in-bounds positions surrounding `c`, including diagonals.
+    pub fn neighbours8(&self, c: Coordinate) -> impl Iterator<Item = Coordinate> + '_ {
+        DIRECTIONS8
+            .iter()
+            .map(move |&d| c + d)
+            .filter(|&n| self.contains(n))
+    }
+
+    /// The cells of row `y`, left to right. Empty if the row is out of bounds.
+    pub fn row(&self, y: usize) -> impl Iterator<Item = &T> {
+        let row: &[T] = if y < self.height {
+            &self.cells[y * self.width..(y + 1) * self.width]
+        } else {
+            &[]
+        };
+        row.iter()
+    }
+
+    /// The cells of column `x`, top to bottom. Empty if the column is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let height = if x < self.width { self.height } else { 0 };
+        (0..height).map(move |y| &self.cells[y * self.width + x])
+    }
+
+    /// Walks from `start` in steps of `step` until leaving the grid. `start` itself is not
+    /// part of the ray.
+    pub fn ray(
+        &self,
+        start: Coordinate,
+        step: Coordinate,
+    ) -> impl Iterator<Item = (Coordinate, &T)> {
+        let mut c = start;
+        std::iter::from_fn(move || {
+            c = c + step;
+            self.get(c).map(|v| (c, v))
+        })
+    }
+}
+
+impl<T> Index<Coordinate> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, c: Coordinate) -> &T {
+        match self.get(c) {
+            Some(v) => v,
+            None => panic!(
+                "{:?} is outside of a {}x{} grid",
+                c, self.width, self.height
+            ),
+        }
+    }
+}
+
+impl<T> IndexMut<Coordinate> for Grid<T> {
+    fn index_mut(&mut self, c: Coordinate) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        match self.get_mut(c) {
+            Some(v) => v,
+            None => panic!("{:?} is outside of a {}x{} grid", c, width, height),
+        }
+    }
+}
+
+/// The smallest and largest component values over a set of positions, as `(min, max)`.
+/// Returns `None` for an empty set.
+pub fn bounds<'a, I>(coords: I) -> Option<(Coordinate, Coordinate)>
+where
+    I: IntoIterator<Item = &'a Coordinate>,
+{
+    coords.into_iter().fold(None, |acc, c| match acc {
+        None => Some((*c, *c)),
+        Some((min, max)) => Some((
+            Coordinate::new(min.x.min(c.x), min.y.min(c.y)),
+            Coordinate::new(max.x.max(c.x), max.y.max(c.y)),
+        )),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<u32> {
+        Grid::parse("123\n456", "a digit", |ch| ch.to_digit(10)).unwrap()
+    }
+
+    #[test]
+    fn test_parse() {
+        let grid = sample();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[Coordinate::new(2, 1)], 6);
+        assert_eq!(grid.get(Coordinate::new(3, 0)), None);
+        assert_eq!(grid.get(Coordinate::new(0, -1)), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let digit = |ch: char| ch.to_digit(10);
+        assert_eq!(
+            Grid::parse("123\n4x6", "a digit", digit),
+            Err(ParseError::new(2, 2, "x", "a digit"))
+        );
+        assert_eq!(
+            Grid::parse("123\n45", "a digit", digit),
+            Err(ParseError::new(
+                2,
+                1,
+                "45",
+                "a row of 3 cells like the ones before"
+            ))
+        );
+        assert_eq!(
+            Grid::parse("", "a digit", digit),
+            Err(ParseError::end_of_input(1, "a row of the map"))
+        );
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let grid = sample();
+        let n4: Vec<Coordinate> = grid.neighbours4(Coordinate::new(0, 0)).collect();
+        assert_eq!(n4, vec![Coordinate::new(1, 0), Coordinate::new(0, 1)]);
+        assert_eq!(grid.neighbours8(Coordinate::new(1, 0)).count(), 5);
+        assert_eq!(grid.neighbours8(Coordinate::new(1, 1)).count(), 5);
+    }
+
+    #[test]
+    fn test_rows_columns_rays() {
+        let grid = sample();
+        assert_eq!(grid.row(1).copied().collect::<Vec<u32>>(), vec![4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<u32>>(), vec![3, 6]);
+        assert_eq!(grid.row(2).count(), 0);
+        let ray: Vec<u32> = grid
+            .ray(Coordinate::new(0, 0), Coordinate::new(1, 0))
+            .map(|(_, v)| *v)
+            .collect();
+        assert_eq!(ray, vec![2, 3]);
+        assert_eq!(grid.ray(Coordinate::new(0, 0), DIRECTIONS4[0]).count(), 0);
+    }
+
+    #[test]
+    fn test_iter_map_position() {
+        let grid = sample();
+        assert_eq!(grid.position(|&v| v == 5), Some(Coordinate::new(1, 1)));
+        assert_eq!(grid.map(|v| v * 2)[Coordinate::new(0, 1)], 8);
+        assert_eq!(grid.iter().count(), 6);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let coords = vec![Coordinate::new(3, -1), Coordinate::new(-2, 4)];
+        assert_eq!(
+            bounds(&coords),
+            Some((Coordinate::new(-2, -1), Coordinate::new(3, 4)))
+        );
+        assert_eq!(bounds(&Vec::new()), None);
+    }
+}
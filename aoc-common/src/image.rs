@@ -0,0 +1,434 @@
+//! Images of the final states and the frames of the simulations, for sharing results without
+//! screenshots. Days describe a state as an [`Image`] and [`save`] it under a short name;
+//! nothing is written unless an export directory is set up with [`init`], which the binaries
+//! do for `--export DIR`.
+
+use crate::visualize::Visualize;
+use crate::Coordinate;
+use std::cell::RefCell;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+/// A pixel's red, green and blue.
+pub type Rgb = [u8; 3];
+
+/// File formats images are written in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageFormat {
+    #[default]
+    Png,
+    /// Binary portable pixmap (`P6`), readable without any library.
+    Ppm,
+}
+
+impl ImageFormat {
+    pub fn extension(self) -> &'static str {
+        match self {
+            ImageFormat::Png => "png",
+            ImageFormat::Ppm => "ppm",
+        }
+    }
+}
+
+impl FromStr for ImageFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ImageFormat, String> {
+        match s {
+            "png" => Ok(ImageFormat::Png),
+            "ppm" => Ok(ImageFormat::Ppm),
+            _ => Err(format!(
+                "unknown image format `{}`, expected `png` or `ppm`",
+                s
+            )),
+        }
+    }
+}
+
+/// An RGB image, row by row from the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Image {
+    width: usize,
+    height: usize,
+    pixels: Vec<Rgb>,
+}
+
+impl Image {
+    /// A `width` by `height` image filled with `background`.
+    pub fn new(width: usize, height: usize, background: Rgb) -> Image {
+        Image {
+            width,
+            height,
+            pixels: vec![background; width * height],
+        }
+    }
+
+    /// One pixel per cell of `width` by `height` cells, coloured by `pixel`.
+    pub fn from_fn<F: FnMut(Coordinate) -> Rgb>(
+        width: usize,
+        height: usize,
+        mut pixel: F,
+    ) -> Image {
+        let mut image = Image::new(width, height, [0; 3]);
+        for y in 0..height {
+            for x in 0..width {
+                image.pixels[y * width + x] = pixel(Coordinate::new(x as isize, y as isize));
+            }
+        }
+        image
+    }
+
+    /// Everything within the bounds of `state`, one pixel in the colour of each glyph.
+    pub fn from_glyphs(state: &dyn Visualize) -> Image {
+        let (min, max) = state.bounds();
+        Image::from_fn(
+            (max.x - min.x + 1).max(0) as usize,
+            (max.y - min.y + 1).max(0) as usize,
+            |c| {
+                state
+                    .glyph(Coordinate::new(min.x + c.x, min.y + c.y))
+                    .colour
+                    .rgb()
+            },
+        )
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height).then(|| self.pixels[y * self.width + x])
+    }
+
+    /// Colours the pixel at `x`, `y`. Panics outside the image.
+    pub fn set(&mut self, x: usize, y: usize, rgb: Rgb) {
+        assert!(x < self.width && y < self.height, "pixel outside the image");
+        self.pixels[y * self.width + x] = rgb;
+    }
+
+    /// Every pixel blown up to a `factor` by `factor` square, so small grids stay visible.
+    pub fn scaled(&self, factor: usize) -> Image {
+        let factor = factor.max(1);
+        Image::from_fn(self.width * factor, self.height * factor, |c| {
+            self.pixels[c.y as usize / factor * self.width + c.x as usize / factor]
+        })
+    }
+
+    pub fn write<W: Write>(&self, out: W, format: ImageFormat) -> io::Result<()> {
+        match format {
+            ImageFormat::Png => self.write_png(out),
+            ImageFormat::Ppm => self.write_ppm(out),
+        }
+    }
+
+    fn write_ppm<W: Write>(&self, mut out: W) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width, self.height)?;
+        out.write_all(&self.pixels.concat())?;
+        out.flush()
+    }
+
+    fn write_png<W: Write>(&self, out: W) -> io::Result<()> {
+        let mut encoder = png::Encoder::new(out, self.width as u32, self.height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().map_err(io::Error::other)?;
+        writer
+            .write_image_data(&self.pixels.concat())
+            .map_err(io::Error::other)?;
+        writer.finish().map_err(io::Error::other)
+    }
+
+    /// Writes the image to `path`, creating the directory if needed.
+    pub fn save(&self, path: &Path, format: ImageFormat) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        self.write(BufWriter::new(File::create(path)?), format)
+    }
+}
+
+/// The colour at `t` from 0 to 1 on a scale from dark blue over green to pale yellow, for
+/// heights and scores.
+pub fn gradient(t: f64) -> Rgb {
+    const STOPS: [Rgb; 3] = [[20, 30, 90], [40, 160, 90], [250, 240, 150]];
+    let t = if t.is_nan() { 0.0 } else { t.clamp(0.0, 1.0) };
+    let scaled = t * (STOPS.len() - 1) as f64;
+    let i = (scaled as usize).min(STOPS.len() - 2);
+    let f = scaled - i as f64;
+    let mut rgb = [0; 3];
+    for (k, channel) in rgb.iter_mut().enumerate() {
+        let (a, b) = (STOPS[i][k] as f64, STOPS[i + 1][k] as f64);
+        *channel = (a + (b - a) * f).round() as u8;
+    }
+    rgb
+}
+
+/// Where images go and how they are named: `<dir>/dayNN-partN-<name>.<ext>` for final states
+/// and `<dir>/dayNN-partN-00001.<ext>` and so on for frames.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Export {
+    dir: PathBuf,
+    format: ImageFormat,
+    frames: bool,
+    prefix: String,
+    next_frame: usize,
+}
+
+impl Export {
+    /// Exports into `dir`, with every frame of the simulations as well if `frames` is set.
+    pub fn new<P: Into<PathBuf>>(dir: P, format: ImageFormat, frames: bool) -> Export {
+        Export {
+            dir: dir.into(),
+            format,
+            frames,
+            prefix: String::from("image"),
+            next_frame: 1,
+        }
+    }
+
+    /// Names the following images after `day` and `part`, with frames numbered from 1.
+    pub fn start(&mut self, day: u8, part: u8) {
+        self.prefix = format!("day{:02}-part{}", day, part);
+        self.next_frame = 1;
+    }
+
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!(
+            "{}-{}.{}",
+            self.prefix,
+            name,
+            self.format.extension()
+        ))
+    }
+
+    pub fn save(&self, name: &str, image: &Image) -> io::Result<PathBuf> {
+        let path = self.path(name);
+        image.save(&path, self.format)?;
+        Ok(path)
+    }
+
+    /// Saves the next numbered frame, if frames are exported at all.
+    pub fn frame(&mut self, image: &Image) -> io::Result<Option<PathBuf>> {
+        if !self.frames {
+            return Ok(None);
+        }
+        let name = format!("{:05}", self.next_frame);
+        self.next_frame += 1;
+        self.save(&name, image).map(Some)
+    }
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static FRAMES: AtomicBool = AtomicBool::new(false);
+static EXPORT: Mutex<Option<Export>> = Mutex::new(None);
+
+thread_local! {
+    /// This thread's copy of [`EXPORT`], so days solved on parallel threads name their images
+    /// after themselves.
+    static CURRENT: RefCell<Option<Export>> = const { RefCell::new(None) };
+}
+
+/// Saves every following image through `export`.
+pub fn init(export: Export) {
+    FRAMES.store(export.frames, Ordering::Relaxed);
+    *EXPORT.lock().unwrap() = Some(export);
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether images are exported, for days that need extra work to build one.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Whether the frames of the simulations are exported as well.
+pub fn recording() -> bool {
+    FRAMES.load(Ordering::Relaxed)
+}
+
+/// Names the following images of this thread, see [`Export::start`]. Called for every part
+/// solved.
+pub fn start(day: u8, part: u8) {
+    let Some(mut export) = EXPORT.lock().unwrap().clone() else {
+        return;
+    };
+    export.start(day, part);
+    CURRENT.with(|current| *current.borrow_mut() = Some(export));
+}
+
+/// Runs `f` on this thread's export, set up from [`EXPORT`] if [`start`] wasn't called yet.
+fn with_export<T, F: FnOnce(&mut Export) -> T>(f: F) -> Option<T> {
+    CURRENT.with(|current| {
+        let mut current = current.borrow_mut();
+        if current.is_none() {
+            *current = EXPORT.lock().unwrap().clone();
+        }
+        current.as_mut().map(f)
+    })
+}
+
+/// Saves the image made by `image` as `name`, if images are exported.
+pub fn save<F: FnOnce() -> Image>(name: &str, image: F) {
+    if !enabled() {
+        return;
+    }
+    let image = image();
+    with_export(|export| report(export.save(name, &image)));
+}
+
+/// Saves `state` as the next frame, if frames are exported.
+pub fn frame(state: &dyn Visualize) {
+    if !recording() {
+        return;
+    }
+    let image = Image::from_glyphs(state);
+    with_export(|export| report(export.frame(&image)));
+}
+
+/// Failing to write an image shouldn't cost the answer, so errors are only printed.
+fn report<T>(result: io::Result<T>) {
+    if let Err(e) = result {
+        eprintln!("Could not export an image: {}", e);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::visualize::{Colour, Glyph};
+    use std::env;
+
+    struct Checkers;
+
+    impl Visualize for Checkers {
+        fn bounds(&self) -> (Coordinate, Coordinate) {
+            (Coordinate::new(-1, -1), Coordinate::new(1, 0))
+        }
+
+        fn glyph(&self, c: Coordinate) -> Glyph {
+            if (c.x + c.y) % 2 == 0 {
+                Glyph::new('#', Colour::Red)
+            } else {
+                Glyph::new('.', Colour::Default)
+            }
+        }
+    }
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-image-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn test_ppm() {
+        let mut image = Image::new(2, 1, [0, 0, 0]);
+        image.set(1, 0, [255, 128, 1]);
+        let mut out: Vec<u8> = Vec::new();
+        image.write(&mut out, ImageFormat::Ppm).unwrap();
+        assert_eq!(out, b"P6\n2 1\n255\n\x00\x00\x00\xff\x80\x01");
+    }
+
+    #[test]
+    fn test_png_round_trip() {
+        let image = Image::from_fn(3, 2, |c| [c.x as u8 * 100, c.y as u8 * 100, 7]);
+        let mut out: Vec<u8> = Vec::new();
+        image.write(&mut out, ImageFormat::Png).unwrap();
+
+        let mut reader = png::Decoder::new(out.as_slice()).read_info().unwrap();
+        let mut data = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut data).unwrap();
+        assert_eq!((info.width, info.height), (3, 2));
+        assert_eq!(info.color_type, png::ColorType::Rgb);
+        assert_eq!(
+            &data[..info.buffer_size()],
+            image.pixels.concat().as_slice()
+        );
+    }
+
+    #[test]
+    fn test_from_glyphs() {
+        let image = Image::from_glyphs(&Checkers).scaled(2);
+        assert_eq!((image.width(), image.height()), (6, 4));
+        let red = Colour::Red.rgb();
+        let white = Colour::Default.rgb();
+        assert_eq!(image.get(0, 0), Some(red));
+        assert_eq!(image.get(1, 1), Some(red));
+        assert_eq!(image.get(2, 0), Some(white));
+        assert_eq!(image.get(2, 2), Some(red));
+        assert_eq!(image.get(6, 0), None);
+    }
+
+    #[test]
+    fn test_gradient() {
+        assert_eq!(gradient(0.0), [20, 30, 90]);
+        assert_eq!(gradient(0.5), [40, 160, 90]);
+        assert_eq!(gradient(1.0), [250, 240, 150]);
+        assert_eq!(gradient(2.0), gradient(1.0));
+        assert_eq!(gradient(f64::NAN), gradient(0.0));
+    }
+
+    #[test]
+    fn test_export() {
+        let dir = temp_dir("export");
+        let mut export = Export::new(&dir, ImageFormat::Ppm, true);
+        export.start(14, 2);
+        let image = Image::new(1, 1, [1, 2, 3]);
+        assert_eq!(
+            export.save("cave", &image).unwrap(),
+            dir.join("day14-part2-cave.ppm")
+        );
+        assert_eq!(
+            export.frame(&image).unwrap(),
+            Some(dir.join("day14-part2-00001.ppm"))
+        );
+        assert_eq!(
+            export.frame(&image).unwrap(),
+            Some(dir.join("day14-part2-00002.ppm"))
+        );
+        assert_eq!(
+            fs::read(dir.join("day14-part2-00002.ppm")).unwrap(),
+            b"P6\n1 1\n255\n\x01\x02\x03"
+        );
+        export.start(14, 1);
+        assert_eq!(export.path("00001"), dir.join("day14-part1-00001.ppm"));
+
+        let mut stills = Export::new(&dir, ImageFormat::Png, false);
+        assert_eq!(stills.frame(&image).unwrap(), None);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_parallel_days() {
+        let dir = temp_dir("parallel");
+        init(Export::new(&dir, ImageFormat::Ppm, false));
+        // both days start before either saves, as when `aoc run --all --jobs 2` solves them
+        let barrier = std::sync::Barrier::new(2);
+        std::thread::scope(|scope| {
+            for day in [3, 4] {
+                let barrier = &barrier;
+                scope.spawn(move || {
+                    start(day, 1);
+                    barrier.wait();
+                    save("state", || Image::new(1, 1, [day, 0, 0]));
+                });
+            }
+        });
+        for day in [3, 4] {
+            let path = dir.join(format!("day{:02}-part1-state.ppm", day));
+            assert_eq!(
+                fs::read(path).unwrap(),
+                [b"P6\n1 1\n255\n".as_slice(), &[day, 0, 0]].concat()
+            );
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
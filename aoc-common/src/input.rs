@@ -0,0 +1,100 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Environment variable that overrides the default input file. `-` reads stdin.
+pub const INPUT_ENV: &str = "AOC_INPUT";
+
+/// Where a puzzle input is read from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Picks the input in order of precedence: an explicit argument, the `AOC_INPUT`
+    /// environment variable, and finally `default`.
+    pub fn resolve<P: Into<PathBuf>>(arg: Option<&str>, default: P) -> InputSource {
+        let env_value = env::var(INPUT_ENV).ok().filter(|v| !v.is_empty());
+        match arg.map(|a| a.to_string()).or(env_value) {
+            Some(value) => InputSource::from_arg(&value),
+            None => InputSource::File(default.into()),
+        }
+    }
+
+    fn from_arg(arg: &str) -> InputSource {
+        if arg == "-" {
+            InputSource::Stdin
+        } else {
+            InputSource::File(PathBuf::from(arg))
+        }
+    }
+
+    pub fn read(&self) -> io::Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+            InputSource::File(path) => read_input(path),
+        }
+    }
+
+    /// A buffered reader over the input, for inputs too large to read at once.
+    pub fn open(&self) -> io::Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::File(path) => Ok(Box::new(BufReader::new(fs::File::open(path)?))),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Reads a whole puzzle input file.
+pub fn read_input<P: AsRef<Path>>(path: P) -> io::Result<String> {
+    fs::read_to_string(path)
+}
+
+/// Splits an input into owned lines.
+pub fn lines(input: &str) -> Vec<String> {
+    input.lines().map(|l| l.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(lines("a\nb\n"), vec!["a".to_string(), "b".to_string()]);
+    }
+
+    #[test]
+    fn test_from_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("some/input"),
+            InputSource::File(PathBuf::from("some/input"))
+        );
+    }
+
+    #[test]
+    fn test_resolve_prefers_argument() {
+        assert_eq!(
+            InputSource::resolve(Some("other"), "input"),
+            InputSource::File(PathBuf::from("other"))
+        );
+    }
+}
@@ -0,0 +1,16 @@
+//! Shared building blocks for the Advent of Code 2022 solutions.
+
+pub mod coordinate;
+pub mod error;
+pub mod grid;
+pub mod image;
+pub mod input;
+pub mod output;
+pub mod solution;
+pub mod trace;
+pub mod visualize;
+
+pub use coordinate::Coordinate;
+pub use error::ParseError;
+pub use grid::Grid;
+pub use solution::{Answer, Part, Runner, Solution};
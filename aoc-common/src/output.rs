@@ -0,0 +1,139 @@
+use crate::error::ParseError;
+use crate::solution::{format_answer, Answer, Part, Runner};
+use serde::ser::{Serialize, Serializer};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// How answers are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// `Part 1: <answer>`, one per line.
+    #[default]
+    Text,
+    /// One JSON object per part and line, see [`Record`].
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format `{}`, expected `text` or `json`", s)),
+        }
+    }
+}
+
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Answer::Number(n) => serializer.serialize_i128(*n),
+            Answer::Text(s) => serializer.serialize_str(s),
+        }
+    }
+}
+
+/// The outcome of solving one part, as printed by `--format json`.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    pub answer: Answer,
+    /// Time spent parsing the input, shared by both parts.
+    pub parse_ns: u128,
+    pub solve_ns: u128,
+    /// Answers that are pictures (day 10's CRT) are also given line by line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub artefact: Option<Vec<String>>,
+}
+
+impl Record {
+    pub fn new(day: u8, part: Part, answer: Answer, parse: Duration, solve: Duration) -> Record {
+        let artefact = match &answer {
+            Answer::Text(s) if s.contains('\n') => Some(s.lines().map(|l| l.to_string()).collect()),
+            _ => None,
+        };
+        Record {
+            day,
+            part: part.number(),
+            answer,
+            parse_ns: parse.as_nanos(),
+            solve_ns: solve.as_nanos(),
+            artefact,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+
+    pub fn format(&self, format: Format) -> String {
+        match format {
+            Format::Text => format_answer(Part::from_number(self.part).unwrap(), &self.answer),
+            Format::Json => self.to_json(),
+        }
+    }
+}
+
+/// Parses `input` once and solves the given parts, timing every step.
+pub fn solve(
+    solution: &dyn Runner,
+    input: &str,
+    parts: &[Part],
+) -> Result<Vec<Record>, ParseError> {
+    let start = Instant::now();
+    let parsed = solution.parse(input)?;
+    let parse = start.elapsed();
+    Ok(parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = solution.solve(parsed.as_ref(), part);
+            Record::new(solution.day(), part, answer, parse, start.elapsed())
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_number() {
+        let record = Record::new(
+            11,
+            Part::Two,
+            Answer::Number(14636993466),
+            Duration::from_nanos(5),
+            Duration::from_nanos(7),
+        );
+        assert_eq!(
+            record.to_json(),
+            r#"{"day":11,"part":2,"answer":14636993466,"parse_ns":5,"solve_ns":7}"#
+        );
+    }
+
+    #[test]
+    fn test_json_picture() {
+        let record = Record::new(
+            10,
+            Part::Two,
+            Answer::Text("#.\n.#".to_string()),
+            Duration::ZERO,
+            Duration::ZERO,
+        );
+        assert_eq!(
+            record.to_json(),
+            r##"{"day":10,"part":2,"answer":"#.\n.#","parse_ns":0,"solve_ns":0,"artefact":["#.",".#"]}"##
+        );
+        assert_eq!(record.format(Format::Text), "Part 2:\n#.\n.#");
+    }
+
+    #[test]
+    fn test_format_from_str() {
+        assert_eq!("json".parse::<Format>(), Ok(Format::Json));
+        assert!("yaml".parse::<Format>().is_err());
+    }
+}
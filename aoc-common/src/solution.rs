@@ -0,0 +1,308 @@
+use crate::error::ParseError;
+use crate::image::{self, Export, ImageFormat};
+use crate::input::InputSource;
+use crate::output::{self, Format};
+use crate::trace;
+use crate::visualize::{self, Options};
+use std::any::Any;
+use std::env;
+use std::fmt;
+use std::process::ExitCode;
+
+/// The two halves of every puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+
+    pub fn from_number(n: u8) -> Option<Part> {
+        match n {
+            1 => Some(Part::One),
+            2 => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+/// The result of one puzzle part. Most parts produce a number, some a string
+/// (day 5's crate tops, day 10's CRT picture).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Answer {
+                    Answer::Number(i128::try_from(n).expect("answer does not fit into an i128"))
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(u32, u64, u128, usize, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Answer {
+        Answer::Text(s)
+    }
+}
+
+/// A single day's puzzle: how to turn the raw input into something the parts can work on,
+/// and how to solve both parts from it.
+pub trait Solution {
+    /// Day of December the puzzle was released on.
+    const DAY: u8;
+
+    type Parsed: 'static;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(parsed: &Self::Parsed) -> Answer;
+    fn part2(parsed: &Self::Parsed) -> Answer;
+
+    fn solve(parsed: &Self::Parsed, part: Part) -> Answer {
+        match part {
+            Part::One => Self::part1(parsed),
+            Part::Two => Self::part2(parsed),
+        }
+    }
+}
+
+/// Object safe view of a [`Solution`], so the runner can keep every day in one list.
+pub trait Runner: Sync {
+    fn day(&self) -> u8;
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError>;
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Answer;
+}
+
+impl<S: Solution + Sync> Runner for S {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn solve(&self, parsed: &dyn Any, part: Part) -> Answer {
+        let parsed = parsed
+            .downcast_ref::<S::Parsed>()
+            .expect("parsed input belongs to a different day");
+        let _span = tracing::info_span!("solve", day = S::DAY, part = part.number()).entered();
+        image::start(S::DAY, part.number());
+        S::solve(parsed, part)
+    }
+}
+
+/// Formats an answer the way every binary prints it. Multi-line answers start on their own line.
+pub fn format_answer(part: Part, answer: &Answer) -> String {
+    let text = answer.to_string();
+    if text.contains('\n') {
+        format!("Part {}:\n{}", part, text)
+    } else {
+        format!("Part {}: {}", part, text)
+    }
+}
+
+const USAGE: &str = "usage: <day> [--format text|json] [--trace] [--visualize [--fps N]] \
+     [--export DIR [--frames] [--image png|ppm]] [input|-]";
+
+/// Entry point shared by the per-day binaries. Takes an optional input path (see
+/// [`InputSource::resolve`], falling back to `default_input`), `--format text|json` and
+/// `--trace` (see [`trace::init`]) and `--visualize` with an optional `--fps N` (see
+/// [`visualize::init`]) and `--export DIR` with `--frames` and `--image png|ppm` (see
+/// [`image::init`]), then prints both parts.
+pub fn main(solution: &dyn Runner, default_input: &str) -> ExitCode {
+    let mut format: Format = Format::Text;
+    let mut input: Option<String> = None;
+    let mut trace_all: bool = false;
+    let mut visualize: bool = false;
+    let mut fps: u32 = Options::DEFAULT_FPS;
+    let mut export: Option<String> = None;
+    let mut frames: bool = false;
+    let mut image_format: ImageFormat = ImageFormat::default();
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let value = match arg.as_str() {
+            "-h" | "--help" => {
+                println!("{}", USAGE);
+                return ExitCode::SUCCESS;
+            }
+            "--trace" => {
+                trace_all = true;
+                continue;
+            }
+            "--visualize" => {
+                visualize = true;
+                continue;
+            }
+            "--export" => {
+                let Some(dir) = args.next() else {
+                    eprintln!("--export needs a directory\n{}", USAGE);
+                    return ExitCode::FAILURE;
+                };
+                export = Some(dir);
+                continue;
+            }
+            "--frames" => {
+                frames = true;
+                continue;
+            }
+            "--image" => {
+                image_format = match args.next().unwrap_or_default().parse() {
+                    Ok(f) => f,
+                    Err(e) => {
+                        eprintln!("{}\n{}", e, USAGE);
+                        return ExitCode::FAILURE;
+                    }
+                };
+                continue;
+            }
+            "--fps" => {
+                let Some(Ok(n)) = args.next().map(|n| n.parse()) else {
+                    eprintln!("--fps needs a number of frames per second\n{}", USAGE);
+                    return ExitCode::FAILURE;
+                };
+                fps = n;
+                continue;
+            }
+            "--format" => args.next().unwrap_or_default(),
+            a if a.starts_with("--format=") => a["--format=".len()..].to_string(),
+            a if a.starts_with('-') && a != "-" => {
+                eprintln!("unknown option `{}`\n{}", a, USAGE);
+                return ExitCode::FAILURE;
+            }
+            _ if input.is_none() => {
+                input = Some(arg);
+                continue;
+            }
+            _ => {
+                eprintln!("unexpected argument `{}`\n{}", arg, USAGE);
+                return ExitCode::FAILURE;
+            }
+        };
+        format = match value.parse() {
+            Ok(f) => f,
+            Err(e) => {
+                eprintln!("{}\n{}", e, USAGE);
+                return ExitCode::FAILURE;
+            }
+        };
+    }
+
+    trace::init(trace_all);
+    if visualize {
+        visualize::init(Options::from_env(fps));
+    }
+    if let Some(dir) = export {
+        image::init(Export::new(dir, image_format, frames));
+    } else if frames {
+        eprintln!("--frames needs --export DIR\n{}", USAGE);
+        return ExitCode::FAILURE;
+    }
+    let source = InputSource::resolve(input.as_deref(), default_input);
+    let input: String = match source.read() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Could not read {}: {}", source, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    match output::solve(solution, &input, &Part::ALL) {
+        Ok(records) => {
+            for record in records {
+                println!("{}", record.format(format));
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Could not parse {}: {}", source, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        const DAY: u8 = 0;
+        type Parsed = Vec<usize>;
+
+        fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+            input
+                .lines()
+                .enumerate()
+                .map(|(i, l)| crate::error::parse_token(i + 1, l, l, "a number"))
+                .collect()
+        }
+
+        fn part1(parsed: &Vec<usize>) -> Answer {
+            parsed.iter().sum::<usize>().into()
+        }
+
+        fn part2(parsed: &Vec<usize>) -> Answer {
+            format!("{:?}", parsed).into()
+        }
+    }
+
+    #[test]
+    fn test_runner() {
+        let runner: &dyn Runner = &Sum;
+        let parsed = runner.parse("1\n2\n3").unwrap();
+        assert_eq!(runner.solve(parsed.as_ref(), Part::One), Answer::Number(6));
+        assert_eq!(
+            runner.solve(parsed.as_ref(), Part::Two),
+            Answer::Text("[1, 2, 3]".to_string())
+        );
+    }
+
+    #[test]
+    fn test_runner_parse_error() {
+        let runner: &dyn Runner = &Sum;
+        let err = runner.parse("1\nx").err().unwrap();
+        assert_eq!(err, ParseError::new(2, 1, "x", "a number"));
+    }
+
+    #[test]
+    fn test_format_answer() {
+        assert_eq!(format_answer(Part::One, &Answer::Number(7)), "Part 1: 7");
+        assert_eq!(
+            format_answer(Part::Two, &Answer::Text("#.\n.#".to_string())),
+            "Part 2:\n#.\n.#"
+        );
+    }
+}
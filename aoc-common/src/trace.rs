@@ -0,0 +1,36 @@
+//! Opt-in diagnostics for the simulations. The days emit [`tracing`] events, a round, grain or
+//! instruction at `debug` and every single step at `trace`, inside a `solve` span naming the
+//! day and part. Nothing is printed unless a subscriber is installed with [`init`].
+
+use std::io;
+use tracing_subscriber::EnvFilter;
+
+/// Environment variable with [`EnvFilter`] directives, e.g. `day11=debug` or `day09=trace`.
+pub const TRACE_ENV: &str = "AOC_TRACE";
+
+/// Prints events to stderr: all of them with `all` (the `--trace` flag), otherwise those
+/// selected by `$AOC_TRACE`, and none if that isn't set either.
+pub fn init(all: bool) {
+    let filter = match env_filter() {
+        Some(filter) => filter,
+        None if all => EnvFilter::new("trace"),
+        None => return,
+    };
+    // A subscriber may already be installed, e.g. by a test harness.
+    let _ = tracing_subscriber::fmt()
+        .with_env_filter(filter)
+        .with_writer(io::stderr)
+        .without_time()
+        .try_init();
+}
+
+fn env_filter() -> Option<EnvFilter> {
+    let directives = std::env::var(TRACE_ENV).ok().filter(|d| !d.is_empty())?;
+    match EnvFilter::try_new(&directives) {
+        Ok(filter) => Some(filter),
+        Err(e) => {
+            eprintln!("ignoring {}=`{}`: {}", TRACE_ENV, directives, e);
+            None
+        }
+    }
+}
@@ -0,0 +1,332 @@
+//! Pictures of the grid simulations in the terminal. A day describes its state with
+//! [`Visualize`] and passes it to [`frame`] whenever something worth seeing happened. Nothing
+//! is drawn unless a renderer is installed with [`init`], which the binaries do for
+//! `--visualize`. The same frames can be saved as images with `--export DIR --frames`, see
+//! [`image::frame`].
+
+use crate::image::{self, Rgb};
+use crate::Coordinate;
+use std::env;
+use std::fmt::Write as _;
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// The terminal colours a glyph can be drawn in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Colour {
+    Default,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Grey,
+}
+
+impl Colour {
+    /// The SGR parameter selecting this colour as foreground.
+    fn sgr(self) -> &'static str {
+        match self {
+            Colour::Default => "39",
+            Colour::Red => "31",
+            Colour::Green => "32",
+            Colour::Yellow => "33",
+            Colour::Blue => "34",
+            Colour::Magenta => "35",
+            Colour::Cyan => "36",
+            Colour::Grey => "90",
+        }
+    }
+
+    /// The colour in images, see [`crate::image`]. `Default` is light on a dark background
+    /// like in most terminals.
+    pub fn rgb(self) -> Rgb {
+        match self {
+            Colour::Default => [220, 220, 220],
+            Colour::Red => [220, 50, 47],
+            Colour::Green => [80, 180, 60],
+            Colour::Yellow => [230, 190, 60],
+            Colour::Blue => [40, 110, 210],
+            Colour::Magenta => [200, 60, 160],
+            Colour::Cyan => [40, 170, 180],
+            Colour::Grey => [50, 50, 55],
+        }
+    }
+}
+
+/// What one cell of a picture looks like.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Glyph {
+    pub symbol: char,
+    pub colour: Colour,
+}
+
+impl Glyph {
+    pub fn new(symbol: char, colour: Colour) -> Glyph {
+        Glyph { symbol, colour }
+    }
+}
+
+/// A state that can be drawn as a picture of glyphs, row by row from the top left.
+pub trait Visualize {
+    /// The top left and bottom right corner of everything there is to see, both inclusive.
+    fn bounds(&self) -> (Coordinate, Coordinate);
+
+    /// The glyph at `c`, which is within [`Visualize::bounds`].
+    fn glyph(&self, c: Coordinate) -> Glyph;
+
+    /// Kept in view when the picture is larger than the viewport, e.g. the head of a rope.
+    /// Without one the viewport shows the top left corner.
+    fn focus(&self) -> Option<Coordinate> {
+        None
+    }
+
+    /// A line of text shown below the picture.
+    fn caption(&self) -> String {
+        String::new()
+    }
+}
+
+/// How frames are drawn.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Options {
+    /// Size of the viewport in glyphs.
+    pub width: usize,
+    pub height: usize,
+    /// Whether glyphs are coloured with ANSI escape codes.
+    pub colour: bool,
+    /// Frames per second the simulation is slowed down to, 0 to draw as fast as possible.
+    pub fps: u32,
+}
+
+impl Options {
+    pub const DEFAULT_FPS: u32 = 30;
+
+    /// Fills the terminal as given by `$COLUMNS` and `$LINES` (80x24 if they aren't set),
+    /// leaving a line for the caption. `$NO_COLOR` turns off colours.
+    pub fn from_env(fps: u32) -> Options {
+        let size = |var: &str, default: usize| {
+            env::var(var)
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|&n: &usize| n > 1)
+                .unwrap_or(default)
+        };
+        Options {
+            width: size("COLUMNS", 80),
+            height: size("LINES", 24) - 1,
+            colour: env::var_os("NO_COLOR").is_none(),
+            fps,
+        }
+    }
+}
+
+/// Draws frames to a terminal, each one over the last.
+pub struct Renderer<W: Write> {
+    out: W,
+    options: Options,
+    last_frame: Option<Instant>,
+}
+
+impl<W: Write> Renderer<W> {
+    pub fn new(out: W, options: Options) -> Renderer<W> {
+        Renderer {
+            out,
+            options,
+            last_frame: None,
+        }
+    }
+
+    /// The part of `state` that fits into the viewport, as its top left and bottom right
+    /// corner. Larger pictures are cut around the focus.
+    pub fn viewport(&self, state: &dyn Visualize) -> (Coordinate, Coordinate) {
+        let (min, max) = state.bounds();
+        let focus = state.focus().unwrap_or(min);
+        let (x0, x1) = window(min.x, max.x, focus.x, self.options.width);
+        let (y0, y1) = window(min.y, max.y, focus.y, self.options.height);
+        (Coordinate::new(x0, y0), Coordinate::new(x1, y1))
+    }
+
+    /// Draws `state` over the previous frame, after waiting for the next frame to be due.
+    pub fn draw(&mut self, state: &dyn Visualize) -> io::Result<()> {
+        let (from, to) = self.viewport(state);
+        let mut frame = String::from(if self.last_frame.is_none() {
+            // clear the screen once, later frames only move to the top left and overwrite
+            "\x1b[2J\x1b[H"
+        } else {
+            "\x1b[H"
+        });
+        frame.push_str(&picture(state, from, to, self.options.colour));
+        frame.push_str(&state.caption());
+        frame.push_str("\x1b[J\n");
+
+        if let (Some(last), Some(interval)) = (self.last_frame, self.interval()) {
+            if let Some(wait) = interval.checked_sub(last.elapsed()) {
+                thread::sleep(wait);
+            }
+        }
+        self.last_frame = Some(Instant::now());
+        self.out.write_all(frame.as_bytes())?;
+        self.out.flush()
+    }
+
+    fn interval(&self) -> Option<Duration> {
+        (self.options.fps > 0).then(|| Duration::from_secs(1) / self.options.fps)
+    }
+}
+
+/// The range of `len` values out of `min..=max` that is centred on `focus` as far as possible.
+fn window(min: isize, max: isize, focus: isize, len: usize) -> (isize, isize) {
+    let len = len.max(1) as isize;
+    if max - min < len {
+        return (min, max);
+    }
+    let start = (focus - len / 2).clamp(min, max - len + 1);
+    (start, start + len - 1)
+}
+
+/// The glyphs of `state` from `from` to `to` (inclusive), one line per row. Colours are only
+/// switched where they change.
+pub fn picture(state: &dyn Visualize, from: Coordinate, to: Coordinate, colour: bool) -> String {
+    let mut out = String::new();
+    for y in from.y..=to.y {
+        let mut current = Colour::Default;
+        for x in from.x..=to.x {
+            let glyph = state.glyph(Coordinate::new(x, y));
+            if colour && glyph.colour != current {
+                write!(out, "\x1b[{}m", glyph.colour.sgr()).unwrap();
+                current = glyph.colour;
+            }
+            out.push(glyph.symbol);
+        }
+        if current != Colour::Default {
+            out.push_str("\x1b[0m");
+        }
+        out.push('\n');
+    }
+    out
+}
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static RENDERER: Mutex<Option<Renderer<io::Stderr>>> = Mutex::new(None);
+
+/// Draws every following [`frame`] to stderr, which keeps stdout for the answers.
+pub fn init(options: Options) {
+    *RENDERER.lock().unwrap() = Some(Renderer::new(io::stderr(), options));
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Whether frames are drawn or saved, for days that need extra work to describe their state.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed) || image::recording()
+}
+
+/// Draws `state` if a renderer is installed, and saves it if frames are exported.
+pub fn frame(state: &dyn Visualize) {
+    if !enabled() {
+        return;
+    }
+    image::frame(state);
+    if let Some(renderer) = RENDERER.lock().unwrap().as_mut() {
+        // a closed terminal shouldn't stop the simulation
+        let _ = renderer.draw(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A diagonal line through an otherwise empty 10x5 picture.
+    struct Diagonal {
+        focus: Option<Coordinate>,
+    }
+
+    impl Visualize for Diagonal {
+        fn bounds(&self) -> (Coordinate, Coordinate) {
+            (Coordinate::new(-2, 0), Coordinate::new(7, 4))
+        }
+
+        fn glyph(&self, c: Coordinate) -> Glyph {
+            if c.x == c.y {
+                Glyph::new('\\', Colour::Red)
+            } else {
+                Glyph::new('.', Colour::Default)
+            }
+        }
+
+        fn focus(&self) -> Option<Coordinate> {
+            self.focus
+        }
+
+        fn caption(&self) -> String {
+            "diagonal".to_string()
+        }
+    }
+
+    fn options(width: usize, height: usize) -> Options {
+        Options {
+            width,
+            height,
+            colour: false,
+            fps: 0,
+        }
+    }
+
+    #[test]
+    fn test_picture() {
+        let state = Diagonal { focus: None };
+        assert_eq!(
+            picture(&state, Coordinate::new(-1, 0), Coordinate::new(2, 2), false),
+            ".\\..\n..\\.\n...\\\n"
+        );
+        assert_eq!(
+            picture(&state, Coordinate::new(-1, 0), Coordinate::new(1, 1), true),
+            ".\x1b[31m\\\x1b[39m.\n..\x1b[31m\\\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn test_viewport() {
+        let whole = Renderer::new(Vec::new(), options(80, 24));
+        assert_eq!(
+            whole.viewport(&Diagonal { focus: None }),
+            (Coordinate::new(-2, 0), Coordinate::new(7, 4))
+        );
+        let small = Renderer::new(Vec::new(), options(4, 3));
+        assert_eq!(
+            small.viewport(&Diagonal { focus: None }),
+            (Coordinate::new(-2, 0), Coordinate::new(1, 2))
+        );
+        assert_eq!(
+            small.viewport(&Diagonal {
+                focus: Some(Coordinate::new(3, 2))
+            }),
+            (Coordinate::new(1, 1), Coordinate::new(4, 3))
+        );
+        assert_eq!(
+            small.viewport(&Diagonal {
+                focus: Some(Coordinate::new(9, 9))
+            }),
+            (Coordinate::new(4, 2), Coordinate::new(7, 4))
+        );
+    }
+
+    #[test]
+    fn test_draw() {
+        let mut renderer = Renderer::new(Vec::new(), options(2, 2));
+        let state = Diagonal {
+            focus: Some(Coordinate::new(1, 1)),
+        };
+        renderer.draw(&state).unwrap();
+        renderer.draw(&state).unwrap();
+        assert_eq!(
+            String::from_utf8(renderer.out).unwrap(),
+            "\x1b[2J\x1b[H\\.\n.\\\ndiagonal\x1b[J\n\x1b[H\\.\n.\\\ndiagonal\x1b[J\n"
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//! Generates one test per example and part from the `sample*.expected` files in the day
+//! directories, see `src/samples.rs`.
+
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn main() {
+    let root = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("..");
+    let mut tests = String::new();
+    // A directory's modification time changes when files are added or removed, which picks
+    // up new days and new examples.
+    println!("cargo:rerun-if-changed={}", root.display());
+    for (day, dir) in day_dirs(&root) {
+        println!("cargo:rerun-if-changed={}", dir.display());
+        let mut sidecars: Vec<PathBuf> = fs::read_dir(&dir)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| {
+                let name = path.file_name().unwrap().to_string_lossy();
+                name.starts_with("sample") && name.ends_with(".expected")
+            })
+            .collect();
+        sidecars.sort();
+        for sidecar in sidecars {
+            println!("cargo:rerun-if-changed={}", sidecar.display());
+            let sample = sidecar.file_stem().unwrap().to_string_lossy().into_owned();
+            let text = fs::read_to_string(&sidecar).unwrap();
+            for part in 1..=2 {
+                if !text
+                    .lines()
+                    .any(|l| l.trim_start().starts_with(&format!("part{}", part)))
+                {
+                    continue;
+                }
+                tests.push_str(&format!(
+                    "#[test]\nfn day{:02}_{}_part{}() {{\n    check({}, {:?}, Part::from_number({}).unwrap());\n}}\n\n",
+                    day,
+                    identifier(&sample),
+                    part,
+                    day,
+                    sample,
+                    part
+                ));
+            }
+        }
+    }
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("sample_tests.rs");
+    fs::write(out, tests).unwrap();
+}
+
+/// The `NN-dayN` directories of the workspace with their day numbers.
+fn day_dirs(root: &Path) -> Vec<(u8, PathBuf)> {
+    let mut dirs: Vec<(u8, PathBuf)> = fs::read_dir(root)
+        .unwrap()
+        .filter_map(|entry| {
+            let path = entry.unwrap().path();
+            let name = path.file_name()?.to_str()?.to_string();
+            let (number, rest) = name.split_once("-day")?;
+            let day: u8 = number.parse().ok()?;
+            (path.is_dir() && rest == day.to_string()).then_some((day, path))
+        })
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+fn identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect()
+}
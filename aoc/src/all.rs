@@ -0,0 +1,205 @@
+use crate::answers::Answers;
+use aoc_common::output::{self, Record};
+use aoc_common::{Answer, Part, Runner};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// The outcome of one day in `aoc run --all`: both parts, or why the input couldn't be used.
+pub struct DayRun {
+    pub day: u8,
+    pub records: Result<Vec<Record>, String>,
+}
+
+/// Solves both parts of every solution from its default input on `jobs` threads (at least
+/// one). The results are in the order of `solutions`.
+pub fn run_all(solutions: &[&dyn Runner], jobs: usize) -> Vec<DayRun> {
+    let next = AtomicUsize::new(0);
+    let runs: Mutex<Vec<Option<DayRun>>> = Mutex::new(solutions.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, solutions.len().max(1)) {
+            scope.spawn(|| loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                let Some(solution) = solutions.get(i) else {
+                    break;
+                };
+                let run = run_day(*solution);
+                runs.lock().unwrap()[i] = Some(run);
+            });
+        }
+    });
+    runs.into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|run| run.unwrap())
+        .collect()
+}
+
+fn run_day(solution: &dyn Runner) -> DayRun {
+    let records = crate::default_source(solution.day()).and_then(|source| {
+        let input = source
+            .read()
+            .map_err(|e| format!("Could not read {}: {}", source, e))?;
+        output::solve(solution, &input, &Part::ALL)
+            .map_err(|e| format!("Could not parse {}: {}", source, e))
+    });
+    DayRun {
+        day: solution.day(),
+        records,
+    }
+}
+
+/// A table of all answers and timings, checked against `answers`, and the number of days that
+/// failed or parts that disagree with a recorded answer.
+pub fn summary(runs: &[DayRun], answers: &Answers) -> (String, usize) {
+    let mut rows: Vec<[String; 6]> = Vec::new();
+    let mut failures: usize = 0;
+    let mut total = Duration::ZERO;
+    for run in runs {
+        let records = match &run.records {
+            Ok(records) => records,
+            Err(e) => {
+                failures += 1;
+                rows.push([
+                    run.day.to_string(),
+                    "-".to_string(),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    format!("FAILED: {}", e),
+                ]);
+                continue;
+            }
+        };
+        if let Some(first) = records.first() {
+            total += Duration::from_nanos(first.parse_ns as u64);
+        }
+        for record in records {
+            total += Duration::from_nanos(record.solve_ns as u64);
+            let part = Part::from_number(record.part).unwrap();
+            let check = match answers.get(run.day, part) {
+                Some(expected) if *expected == record.answer => "ok".to_string(),
+                Some(expected) => {
+                    failures += 1;
+                    format!("MISMATCH, expected {}", cell(expected))
+                }
+                None => "-".to_string(),
+            };
+            rows.push([
+                run.day.to_string(),
+                record.part.to_string(),
+                cell(&record.answer),
+                format!("{:.2?}", Duration::from_nanos(record.parse_ns as u64)),
+                format!("{:.2?}", Duration::from_nanos(record.solve_ns as u64)),
+                check,
+            ]);
+        }
+    }
+
+    let width = rows
+        .iter()
+        .map(|r| r[2].chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("Answer".len());
+    let mut out = format!(
+        "{:>3}  {:>4}  {:<width$}  {:>10}  {:>10}  {}\n",
+        "Day",
+        "Part",
+        "Answer",
+        "Parse",
+        "Solve",
+        "Check",
+        width = width
+    );
+    for r in &rows {
+        writeln!(
+            out,
+            "{:>3}  {:>4}  {:<width$}  {:>10}  {:>10}  {}",
+            r[0],
+            r[1],
+            r[2],
+            r[3],
+            r[4],
+            r[5],
+            width = width
+        )
+        .unwrap();
+    }
+    writeln!(out, "Total: {:.2?}", total).unwrap();
+    (out, failures)
+}
+
+/// Pictures (day 10's CRT) don't fit into a table cell.
+fn cell(answer: &Answer) -> String {
+    match answer {
+        Answer::Text(s) if s.contains('\n') => format!("<picture, {} lines>", s.lines().count()),
+        _ => answer.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(day: u8, part: Part, answer: Answer) -> Record {
+        Record::new(
+            day,
+            part,
+            answer,
+            Duration::from_micros(1),
+            Duration::from_micros(2),
+        )
+    }
+
+    #[test]
+    fn test_summary() {
+        let answers = Answers::parse("[day01]\npart1 = 1\npart2 = 2\n").unwrap();
+        let runs = vec![
+            DayRun {
+                day: 1,
+                records: Ok(vec![
+                    record(1, Part::One, Answer::Number(1)),
+                    record(1, Part::Two, Answer::Number(3)),
+                ]),
+            },
+            DayRun {
+                day: 10,
+                records: Ok(vec![record(
+                    10,
+                    Part::Two,
+                    Answer::Text("#.\n.#".to_string()),
+                )]),
+            },
+            DayRun {
+                day: 11,
+                records: Err("Could not read input".to_string()),
+            },
+        ];
+        let (table, failures) = summary(&runs, &answers);
+        assert_eq!(failures, 2);
+        assert_eq!(
+            table,
+            "\
+Day  Part  Answer                   Parse       Solve  Check
+  1     1  1                       1.00µs      2.00µs  ok
+  1     2  3                       1.00µs      2.00µs  MISMATCH, expected 2
+ 10     2  <picture, 2 lines>      1.00µs      2.00µs  -
+ 11     -                                              FAILED: Could not read input
+Total: 8.00µs
+"
+        );
+    }
+
+    #[test]
+    fn test_run_all_keeps_order() {
+        let solutions: Vec<&dyn Runner> = vec![&day06::Day06, &day01::Day01, &day04::Day04];
+        let runs = run_all(&solutions, 2);
+        let days: Vec<u8> = runs.iter().map(|r| r.day).collect();
+        assert_eq!(days, vec![6, 1, 4]);
+        let (_, failures) = summary(&runs, &Answers::load(Answers::default_path()).unwrap());
+        assert_eq!(failures, 0);
+    }
+}
@@ -0,0 +1,142 @@
+use aoc_common::{Answer, Part};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// The known-correct answers for our own puzzle inputs, keyed by day and part.
+///
+/// They are stored as TOML with one table per day:
+///
+/// ```toml
+/// [day07]
+/// part1 = 1844187
+/// part2 = 4978279
+/// ```
+#[derive(Debug, Default)]
+pub struct Answers {
+    answers: BTreeMap<(u8, Part), Answer>,
+}
+
+#[derive(Deserialize)]
+struct DayAnswers {
+    part1: Option<toml::Value>,
+    part2: Option<toml::Value>,
+}
+
+impl Answers {
+    /// `answers.toml` in the workspace root.
+    pub fn default_path() -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join("answers.toml")
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Answers, String> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        Answers::parse(&text).map_err(|e| format!("Could not parse {}: {}", path.display(), e))
+    }
+
+    pub fn parse(text: &str) -> Result<Answers, String> {
+        let days: BTreeMap<String, DayAnswers> = toml::from_str(text).map_err(|e| e.to_string())?;
+        let mut answers: BTreeMap<(u8, Part), Answer> = BTreeMap::new();
+        for (key, day_answers) in days {
+            let day: u8 = key
+                .strip_prefix("day")
+                .and_then(|d| d.parse().ok())
+                .ok_or(format!("expected a table named `dayNN`, found `{}`", key))?;
+            for (part, answer) in day_answers.into_answers(&key)? {
+                answers.insert((day, part), answer);
+            }
+        }
+        Ok(Answers { answers })
+    }
+
+    pub fn get(&self, day: u8, part: Part) -> Option<&Answer> {
+        self.answers.get(&(day, part))
+    }
+}
+
+/// Parses the expected answers of one example, stored next to it as `<sample>.expected` in
+/// the format of a single day's table:
+///
+/// ```toml
+/// part1 = 95437
+/// part2 = 24933642
+/// ```
+pub fn parse_expected(text: &str) -> Result<BTreeMap<Part, Answer>, String> {
+    let day_answers: DayAnswers = toml::from_str(text).map_err(|e| e.to_string())?;
+    Ok(day_answers.into_answers("expected")?.into_iter().collect())
+}
+
+impl DayAnswers {
+    /// The recorded answers, with `key` naming the table in errors.
+    fn into_answers(self, key: &str) -> Result<Vec<(Part, Answer)>, String> {
+        let mut answers: Vec<(Part, Answer)> = Vec::new();
+        for (part, value) in [(Part::One, self.part1), (Part::Two, self.part2)] {
+            let answer = match value {
+                None => continue,
+                Some(toml::Value::Integer(n)) => Answer::Number(n.into()),
+                Some(toml::Value::String(s)) => Answer::Text(s),
+                Some(other) => {
+                    return Err(format!(
+                        "{}.part{}: expected a number or a string, found `{}`",
+                        key, part, other
+                    ))
+                }
+            };
+            answers.push((part, answer));
+        }
+        Ok(answers)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let answers = Answers::parse(
+            r#"
+[day05]
+part1 = "CMZ"
+
+[day07]
+part1 = 95437
+part2 = 24933642
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            answers.get(5, Part::One),
+            Some(&Answer::Text("CMZ".to_string()))
+        );
+        assert_eq!(answers.get(5, Part::Two), None);
+        assert_eq!(answers.get(7, Part::Two), Some(&Answer::Number(24933642)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Answers::parse("[seven]\npart1 = 1").is_err());
+        assert!(Answers::parse("[day07]\npart1 = 1.5").is_err());
+    }
+
+    #[test]
+    fn test_parse_expected() {
+        let expected = parse_expected("part1 = \"CMZ\"").unwrap();
+        assert_eq!(
+            expected.get(&Part::One),
+            Some(&Answer::Text("CMZ".to_string()))
+        );
+        assert_eq!(expected.get(&Part::Two), None);
+        assert!(parse_expected("part2 = true").is_err());
+    }
+
+    #[test]
+    fn test_committed_answers_parse() {
+        assert!(Answers::load(Answers::default_path()).is_ok());
+    }
+}
@@ -0,0 +1,195 @@
+use aoc_common::{ParseError, Part, Runner};
+use serde::Serialize;
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Timings of one phase (parsing or one of the parts) over all iterations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Panics on an empty sample set; [`bench`] always takes at least one sample.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Stats {
+        samples.sort_unstable();
+        let mid = samples.len() / 2;
+        let median = if samples.len().is_multiple_of(2) {
+            (samples[mid - 1] + samples[mid]) / 2
+        } else {
+            samples[mid]
+        };
+        Stats {
+            min: samples[0],
+            median,
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayBench {
+    pub day: u8,
+    pub iterations: usize,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl DayBench {
+    pub fn phases(&self) -> [(&'static str, Stats); 3] {
+        [
+            ("parse", self.parse),
+            ("part1", self.part1),
+            ("part2", self.part2),
+        ]
+    }
+}
+
+/// Parses and solves `input` `iterations` times (at least once), timing every phase separately.
+pub fn bench(
+    solution: &dyn Runner,
+    input: &str,
+    iterations: usize,
+) -> Result<DayBench, ParseError> {
+    let iterations = iterations.max(1);
+    let mut parse: Vec<Duration> = Vec::with_capacity(iterations);
+    let mut part1: Vec<Duration> = Vec::with_capacity(iterations);
+    let mut part2: Vec<Duration> = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = solution.parse(input)?;
+        parse.push(start.elapsed());
+        for (part, samples) in [(Part::One, &mut part1), (Part::Two, &mut part2)] {
+            let start = Instant::now();
+            black_box(solution.solve(parsed.as_ref(), part));
+            samples.push(start.elapsed());
+        }
+    }
+    Ok(DayBench {
+        day: solution.day(),
+        iterations,
+        parse: Stats::from_samples(parse),
+        part1: Stats::from_samples(part1),
+        part2: Stats::from_samples(part2),
+    })
+}
+
+#[derive(Serialize)]
+struct Record {
+    day: u8,
+    phase: &'static str,
+    iterations: usize,
+    min_ns: u128,
+    median_ns: u128,
+    max_ns: u128,
+}
+
+fn records(results: &[DayBench]) -> Vec<Record> {
+    results
+        .iter()
+        .flat_map(|r| {
+            r.phases().map(|(phase, stats)| Record {
+                day: r.day,
+                phase,
+                iterations: r.iterations,
+                min_ns: stats.min.as_nanos(),
+                median_ns: stats.median.as_nanos(),
+                max_ns: stats.max.as_nanos(),
+            })
+        })
+        .collect()
+}
+
+pub fn to_text(results: &[DayBench]) -> String {
+    let mut out = format!(
+        "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}\n",
+        "Day", "Phase", "Min", "Median", "Max"
+    );
+    for r in results {
+        for (phase, stats) in r.phases() {
+            writeln!(
+                out,
+                "{:>3}  {:<5}  {:>12}  {:>12}  {:>12}",
+                r.day,
+                phase,
+                format!("{:.2?}", stats.min),
+                format!("{:.2?}", stats.median),
+                format!("{:.2?}", stats.max)
+            )
+            .unwrap();
+        }
+    }
+    out
+}
+
+/// One record per day and phase, durations in nanoseconds.
+pub fn to_json(results: &[DayBench]) -> String {
+    serde_json::to_string_pretty(&records(results)).unwrap()
+}
+
+/// One row per day and phase, durations in nanoseconds.
+pub fn to_csv(results: &[DayBench]) -> String {
+    let mut out = String::from("day,phase,iterations,min_ns,median_ns,max_ns\n");
+    for r in records(results) {
+        writeln!(
+            out,
+            "{},{},{},{},{},{}",
+            r.day, r.phase, r.iterations, r.min_ns, r.median_ns, r.max_ns
+        )
+        .unwrap();
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(n: u64) -> Duration {
+        Duration::from_millis(n)
+    }
+
+    #[test]
+    fn test_stats() {
+        assert_eq!(
+            Stats::from_samples(vec![ms(5), ms(1), ms(3)]),
+            Stats {
+                min: ms(1),
+                median: ms(3),
+                max: ms(5)
+            }
+        );
+        assert_eq!(
+            Stats::from_samples(vec![ms(4), ms(1), ms(2), ms(9)]).median,
+            ms(3)
+        );
+    }
+
+    #[test]
+    fn test_csv() {
+        let stats = Stats {
+            min: ms(1),
+            median: ms(2),
+            max: ms(3),
+        };
+        let results = [DayBench {
+            day: 7,
+            iterations: 3,
+            parse: stats,
+            part1: stats,
+            part2: stats,
+        }];
+        let csv = to_csv(&results);
+        let mut lines = csv.lines();
+        assert_eq!(
+            lines.next(),
+            Some("day,phase,iterations,min_ns,median_ns,max_ns")
+        );
+        assert_eq!(lines.next(), Some("7,parse,3,1000000,2000000,3000000"));
+        assert_eq!(csv.lines().count(), 4);
+    }
+}
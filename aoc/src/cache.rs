@@ -0,0 +1,227 @@
+use sha2::{Digest, Sha256};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Environment variable with the directory downloaded inputs are kept in.
+pub const CACHE_ENV: &str = "AOC_CACHE";
+/// Environment variable with the server inputs are fetched from, e.g. a local stand-in in CI.
+pub const ENDPOINT_ENV: &str = "AOC_ENDPOINT";
+/// Environment variable with the session cookie used to fetch inputs.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Gets the puzzle input of a day that isn't cached yet.
+pub trait Fetcher {
+    fn fetch(&self, day: u8) -> Result<String, String>;
+}
+
+/// Fetches inputs over HTTP from `<endpoint>/2022/day/<day>/input`, authenticated with the
+/// session cookie of a logged in user.
+pub struct HttpFetcher {
+    endpoint: String,
+    session: Option<String>,
+}
+
+impl HttpFetcher {
+    pub const DEFAULT_ENDPOINT: &'static str = "https://adventofcode.com";
+
+    pub fn new(endpoint: &str, session: Option<String>) -> HttpFetcher {
+        HttpFetcher {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            session,
+        }
+    }
+
+    /// Configured by `AOC_ENDPOINT` and `AOC_SESSION`. The session is only needed once
+    /// something actually has to be fetched.
+    pub fn from_env() -> HttpFetcher {
+        let endpoint = env::var(ENDPOINT_ENV).unwrap_or(HttpFetcher::DEFAULT_ENDPOINT.to_string());
+        HttpFetcher::new(
+            &endpoint,
+            env::var(SESSION_ENV).ok().filter(|s| !s.is_empty()),
+        )
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, day: u8) -> Result<String, String> {
+        let Some(session) = &self.session else {
+            return Err(format!(
+                "The input of day {} is not cached, set {} to fetch it",
+                day, SESSION_ENV
+            ));
+        };
+        let url = format!("{}/2022/day/{}/input", self.endpoint, day);
+        ureq::get(&url)
+            .set("Cookie", &format!("session={}", session))
+            .set(
+                "User-Agent",
+                "github.com/CaptainJack42/aoc-2022 input cache",
+            )
+            .call()
+            .map_err(|e| format!("Could not fetch {}: {}", url, e))?
+            .into_string()
+            .map_err(|e| format!("Could not read {}: {}", url, e))
+    }
+}
+
+/// Downloaded inputs, stored as `dayNN.txt` with their SHA-256 in `dayNN.sha256`. A cached
+/// input is never downloaded again; one that no longer matches its checksum is an error.
+pub struct InputCache {
+    dir: PathBuf,
+}
+
+impl InputCache {
+    pub fn new<P: Into<PathBuf>>(dir: P) -> InputCache {
+        InputCache { dir: dir.into() }
+    }
+
+    /// `$AOC_CACHE`, or `.cache/inputs` in the workspace root.
+    pub fn default_dir() -> PathBuf {
+        match env::var(CACHE_ENV) {
+            Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+                .join("..")
+                .join(".cache")
+                .join("inputs"),
+        }
+    }
+
+    pub fn input_path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{:02}.txt", day))
+    }
+
+    fn checksum_path(&self, day: u8) -> PathBuf {
+        self.dir.join(format!("day{:02}.sha256", day))
+    }
+
+    /// The cached input of `day`, if there is one and it is intact.
+    pub fn cached(&self, day: u8) -> Result<Option<String>, String> {
+        let path = self.input_path(day);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let input = fs::read_to_string(&path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        let checksum_path = self.checksum_path(day);
+        let checksum = fs::read_to_string(&checksum_path)
+            .map_err(|e| format!("Could not read {}: {}", checksum_path.display(), e))?;
+        if checksum.trim() != sha256(&input) {
+            return Err(format!(
+                "{} does not match its checksum, delete it to fetch it again",
+                path.display()
+            ));
+        }
+        Ok(Some(input))
+    }
+
+    /// The input of `day`, fetched and stored first unless it is cached already.
+    pub fn get(&self, day: u8, fetcher: &dyn Fetcher) -> Result<String, String> {
+        if let Some(input) = self.cached(day)? {
+            return Ok(input);
+        }
+        let input = fetcher.fetch(day)?;
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Could not create {}: {}", self.dir.display(), e))?;
+        for (path, text) in [
+            (self.checksum_path(day), sha256(&input) + "\n"),
+            (self.input_path(day), input.clone()),
+        ] {
+            fs::write(&path, text)
+                .map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+        }
+        Ok(input)
+    }
+}
+
+/// Lower case hex SHA-256 of `text`.
+pub fn sha256(text: &str) -> String {
+    Sha256::digest(text.as_bytes())
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    struct Counting {
+        calls: Cell<usize>,
+    }
+
+    impl Fetcher for Counting {
+        fn fetch(&self, day: u8) -> Result<String, String> {
+            self.calls.set(self.calls.get() + 1);
+            Ok(format!("input of day {}\n", day))
+        }
+    }
+
+    fn temp_cache(name: &str) -> InputCache {
+        let dir = env::temp_dir().join(format!("aoc-cache-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        InputCache::new(dir)
+    }
+
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            sha256("abc"),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+
+    #[test]
+    fn test_fetches_once() {
+        let cache = temp_cache("once");
+        let fetcher = Counting {
+            calls: Cell::new(0),
+        };
+        assert_eq!(cache.get(3, &fetcher).unwrap(), "input of day 3\n");
+        assert_eq!(cache.get(3, &fetcher).unwrap(), "input of day 3\n");
+        assert_eq!(fetcher.calls.get(), 1);
+
+        fs::write(cache.input_path(3), "tampered").unwrap();
+        assert!(cache.get(3, &fetcher).is_err());
+        assert_eq!(fetcher.calls.get(), 1);
+        fs::remove_dir_all(&cache.dir).unwrap();
+    }
+
+    #[test]
+    fn test_http_fetcher() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}/", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request: Vec<String> = Vec::new();
+            for line in BufReader::new(&stream).lines() {
+                let line = line.unwrap();
+                if line.is_empty() {
+                    break;
+                }
+                request.push(line);
+            }
+            let body = "1000\n2000\n";
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            )
+            .unwrap();
+            request
+        });
+
+        let fetcher = HttpFetcher::new(&endpoint, Some("secret".to_string()));
+        assert_eq!(fetcher.fetch(1).unwrap(), "1000\n2000\n");
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2022/day/1/input HTTP/1.1");
+        assert!(request.iter().any(|h| h == "Cookie: session=secret"));
+
+        assert!(HttpFetcher::new(&endpoint, None).fetch(1).is_err());
+    }
+}
@@ -0,0 +1,39 @@
+//! The `aoc` runner: one entry point for every day of the workspace.
+
+pub mod all;
+pub mod answers;
+pub mod bench;
+pub mod cache;
+#[cfg(test)]
+mod mangled;
+pub mod registry;
+#[cfg(test)]
+mod samples;
+pub mod scaffold;
+
+use aoc_common::input::InputSource;
+use aoc_common::Runner;
+use cache::{HttpFetcher, InputCache};
+use std::any::Any;
+
+/// A day's committed `input` file or, for days without one, its copy in the input cache,
+/// which is fetched first if it isn't there yet.
+pub fn default_source(day: u8) -> Result<InputSource, String> {
+    let committed = registry::default_input(day);
+    if committed.exists() {
+        return Ok(InputSource::File(committed));
+    }
+    let cache = InputCache::new(InputCache::default_dir());
+    cache.get(day, &HttpFetcher::from_env())?;
+    Ok(InputSource::File(cache.input_path(day)))
+}
+
+/// Reads and parses a day's input, describing what went wrong if either step fails.
+pub fn load(solution: &dyn Runner, source: &InputSource) -> Result<Box<dyn Any>, String> {
+    let input: String = source
+        .read()
+        .map_err(|e| format!("Could not read {}: {}", source, e))?;
+    solution
+        .parse(&input)
+        .map_err(|e| format!("Could not parse {}: {}", source, e))
+}
@@ -0,0 +1,373 @@
+use aoc::all;
+use aoc::answers::Answers;
+use aoc::bench;
+use aoc::cache::{self, HttpFetcher, InputCache};
+use aoc::registry;
+use aoc::scaffold;
+use aoc_common::image::{self, Export, ImageFormat};
+use aoc_common::input::InputSource;
+use aoc_common::output::{self, Format};
+use aoc_common::visualize;
+use aoc_common::{Part, Runner};
+use clap::{Parser, Subcommand, ValueEnum};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+    /// Print every simulation step to stderr. `$AOC_TRACE` selects levels per day instead,
+    /// e.g. `day11=debug`
+    #[arg(long, global = true)]
+    trace: bool,
+    /// Draw the simulations of days 8, 9, 12 and 14 in the terminal (on stderr)
+    #[arg(long, global = true)]
+    visualize: bool,
+    /// Frames per second for `--visualize`, 0 for as fast as possible
+    #[arg(long, global = true, default_value_t = visualize::Options::DEFAULT_FPS, requires = "visualize")]
+    fps: u32,
+    /// Save images of the final states of days 8, 10, 12 and 14 into this directory
+    #[arg(long, global = true, value_name = "DIR")]
+    export: Option<PathBuf>,
+    /// Save every frame of the simulations with `--export` as well
+    #[arg(long, global = true, requires = "export")]
+    frames: bool,
+    /// `png` or `ppm`
+    #[arg(long, global = true, default_value = "png", requires = "export")]
+    image: ImageFormat,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve one day, both parts unless `--part` is given, or every day with `--all`
+    Run {
+        #[arg(short, long, required_unless_present = "all")]
+        day: Option<u8>,
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Input file, `-` for stdin. Defaults to `$AOC_INPUT`, then the day's `input` file
+        #[arg(short, long)]
+        input: Option<String>,
+        /// `text` or `json` (one record per part and line)
+        #[arg(short, long, default_value = "text")]
+        format: Format,
+        /// Solve every day from its default input, print a summary and check it against
+        /// `answers.toml`
+        #[arg(long, conflicts_with_all = ["day", "part", "input"])]
+        all: bool,
+        /// Threads to solve the days on with `--all`
+        #[arg(short, long, default_value_t = 1, requires = "all")]
+        jobs: usize,
+    },
+    /// Solve every day from its committed input and compare against the recorded answers
+    Verify {
+        /// Only verify this day
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// Answers file, defaults to `answers.toml` in the repository root
+        #[arg(short, long)]
+        answers: Option<PathBuf>,
+    },
+    /// Time parsing and both parts of every day (or one day) separately
+    Bench {
+        /// Only benchmark this day
+        #[arg(short, long)]
+        day: Option<u8>,
+        /// How often every day is parsed and solved
+        #[arg(short = 'n', long, default_value_t = 10)]
+        iterations: usize,
+        #[arg(short, long, value_enum, default_value_t = BenchFormat::Text)]
+        format: BenchFormat,
+    },
+    /// Download inputs into the cache (`$AOC_CACHE`) unless they are cached already
+    Fetch {
+        /// Only fetch this day, defaults to every solved day
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: Option<u8>,
+    },
+    /// Create and register the crate for a new day
+    New {
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum BenchFormat {
+    Text,
+    Json,
+    Csv,
+}
+
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    aoc_common::trace::init(cli.trace);
+    if cli.visualize {
+        visualize::init(visualize::Options::from_env(cli.fps));
+    }
+    if let Some(dir) = cli.export {
+        image::init(Export::new(dir, cli.image, cli.frames));
+    }
+    match cli.command {
+        Command::Run {
+            all: true,
+            format,
+            jobs,
+            ..
+        } => run_all(format, jobs),
+        Command::Run {
+            day,
+            part,
+            input,
+            format,
+            ..
+        } => run(
+            day.unwrap(),
+            part.and_then(Part::from_number),
+            input.as_deref(),
+            format,
+        ),
+        Command::Verify { day, answers } => verify(day, answers),
+        Command::Bench {
+            day,
+            iterations,
+            format,
+        } => bench(day, iterations, format),
+        Command::Fetch { day } => fetch(day),
+        Command::New { day } => new(day),
+    }
+}
+
+fn run(day: u8, part: Option<Part>, input: Option<&str>, format: Format) -> ExitCode {
+    let Some(solution) = registry::find(day) else {
+        eprintln!("Day {} is not solved (yet)", day);
+        return ExitCode::FAILURE;
+    };
+    let source = match InputSource::resolve(input, registry::default_input(day)) {
+        InputSource::File(path) if path == registry::default_input(day) => {
+            match aoc::default_source(day) {
+                Ok(source) => source,
+                Err(e) => {
+                    eprintln!("{}", e);
+                    return ExitCode::FAILURE;
+                }
+            }
+        }
+        source => source,
+    };
+    let input: String = match source.read() {
+        Ok(input) => input,
+        Err(e) => {
+            eprintln!("Could not read {}: {}", source, e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let parts: Vec<Part> = match part {
+        Some(p) => vec![p],
+        None => Part::ALL.to_vec(),
+    };
+    match output::solve(solution, &input, &parts) {
+        Ok(records) => {
+            for record in records {
+                println!("{}", record.format(format));
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Could not parse {}: {}", source, e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run_all(format: Format, jobs: usize) -> ExitCode {
+    let answers = match Answers::load(Answers::default_path()) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let runs = all::run_all(registry::SOLUTIONS, jobs);
+    let (table, failures) = all::summary(&runs, &answers);
+    match format {
+        Format::Text => print!("{}", table),
+        Format::Json => {
+            for run in &runs {
+                match &run.records {
+                    Ok(records) => records.iter().for_each(|r| println!("{}", r.to_json())),
+                    Err(e) => eprintln!("Day {:>2}: {}", run.day, e),
+                }
+            }
+        }
+    }
+    if failures > 0 {
+        eprintln!("{} failure(s)", failures);
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// The requested day, or all of them.
+fn select(day: Option<u8>) -> Option<Vec<&'static dyn Runner>> {
+    match day {
+        Some(d) => match registry::find(d) {
+            Some(s) => Some(vec![s]),
+            None => {
+                eprintln!("Day {} is not solved (yet)", d);
+                None
+            }
+        },
+        None => Some(registry::SOLUTIONS.to_vec()),
+    }
+}
+
+fn verify(day: Option<u8>, answers: Option<PathBuf>) -> ExitCode {
+    let answers = match Answers::load(answers.unwrap_or_else(Answers::default_path)) {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+    let Some(solutions) = select(day) else {
+        return ExitCode::FAILURE;
+    };
+
+    let mut failures: usize = 0;
+    for solution in solutions {
+        let day = solution.day();
+        let parsed = match aoc::default_source(day).and_then(|source| aoc::load(solution, &source))
+        {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                println!("Day {:>2}: {}", day, e);
+                failures += 1;
+                continue;
+            }
+        };
+        for part in Part::ALL {
+            let answer = solution.solve(parsed.as_ref(), part);
+            match answers.get(day, part) {
+                Some(expected) if *expected == answer => {
+                    println!("Day {:>2} part {}: ok", day, part);
+                }
+                Some(expected) => {
+                    println!(
+                        "Day {:>2} part {}: MISMATCH, expected {}, got {}",
+                        day,
+                        part,
+                        one_line(expected),
+                        one_line(&answer)
+                    );
+                    failures += 1;
+                }
+                None => println!(
+                    "Day {:>2} part {}: no recorded answer, got {}",
+                    day,
+                    part,
+                    one_line(&answer)
+                ),
+            }
+        }
+    }
+
+    if failures > 0 {
+        println!("{} failure(s)", failures);
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+/// Multi-line answers (day 10's CRT) are shown with `\n` so reports stay one line per part.
+fn one_line(answer: &aoc_common::Answer) -> String {
+    answer.to_string().replace('\n', "\\n")
+}
+
+fn bench(day: Option<u8>, iterations: usize, format: BenchFormat) -> ExitCode {
+    let Some(solutions) = select(day) else {
+        return ExitCode::FAILURE;
+    };
+    if cfg!(debug_assertions) {
+        eprintln!("Benchmarking a debug build, use `cargo run --release` for meaningful timings");
+    }
+
+    let mut results: Vec<bench::DayBench> = Vec::new();
+    for solution in solutions {
+        let result = aoc::default_source(solution.day()).and_then(|source| {
+            let input = source
+                .read()
+                .map_err(|e| format!("Could not read {}: {}", source, e))?;
+            bench::bench(solution, &input, iterations)
+                .map_err(|e| format!("Could not parse {}: {}", source, e))
+        });
+        match result {
+            Ok(r) => results.push(r),
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let out = match format {
+        BenchFormat::Text => bench::to_text(&results),
+        BenchFormat::Json => bench::to_json(&results) + "\n",
+        BenchFormat::Csv => bench::to_csv(&results),
+    };
+    print!("{}", out);
+    ExitCode::SUCCESS
+}
+
+fn fetch(day: Option<u8>) -> ExitCode {
+    let days: Vec<u8> = match day {
+        Some(d) => vec![d],
+        None => registry::SOLUTIONS.iter().map(|s| s.day()).collect(),
+    };
+    let cache = InputCache::new(InputCache::default_dir());
+    let fetcher = HttpFetcher::from_env();
+    for day in days {
+        match cache.get(day, &fetcher) {
+            Ok(input) => println!(
+                "Day {:>2}: {} ({})",
+                day,
+                cache.input_path(day).display(),
+                cache::sha256(&input)
+            ),
+            Err(e) => {
+                eprintln!("{}", e);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn new(day: u8) -> ExitCode {
+    if registry::find(day).is_some() {
+        eprintln!("Day {} is already solved", day);
+        return ExitCode::FAILURE;
+    }
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+    let root = root.canonicalize().unwrap_or(root);
+    match scaffold::new_day(&root, day) {
+        Ok(touched) => {
+            for path in touched {
+                println!("{}", path.display());
+            }
+            println!(
+                "Put the example into {0}/sample and your puzzle input into {0}/input",
+                root.join(format!("{:02}-day{}", day, day)).display()
+            );
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,147 @@
+//! Every parser returns an error instead of panicking, whatever it is fed, and every input a
+//! parser accepts can be solved without panicking. These tests mangle the example inputs on
+//! stable Rust; `fuzz/` has coverage-guided targets for the same days.
+
+use crate::registry;
+use aoc_common::{Part, Runner};
+use proptest::prelude::*;
+use std::panic::{self, AssertUnwindSafe};
+
+/// Fragments that make the parsers take unusual paths: separators, huge and negative numbers,
+/// multi-byte characters and the keywords of the different input formats.
+const FRAGMENTS: &[&str] = &[
+    "\n",
+    "\n\n",
+    " ",
+    "-",
+    ",",
+    " -> ",
+    "[",
+    "]",
+    "[A]",
+    "$ cd ",
+    "$ ls",
+    "dir ",
+    "..",
+    "/",
+    "move ",
+    " from ",
+    " to ",
+    "addx ",
+    "noop",
+    "Monkey ",
+    "  Operation: new = old * old",
+    "  Test: divisible by ",
+    "    If true: throw to monkey ",
+    "S",
+    "E",
+    "0",
+    "-1",
+    "99999999999999999999",
+    "4294967296",
+    "18446744073709551615",
+    "ä",
+    "€",
+    "🦀",
+    "\r\n",
+];
+
+/// Numbers at the edges of the integer types the parsers use.
+const NUMBERS: &[&str] = &[
+    "0",
+    "-1",
+    "65536",
+    "4294967296",
+    "9223372036854775807",
+    "-9223372036854775808",
+    "18446744073709551615",
+];
+
+#[derive(Debug, Clone)]
+enum Edit {
+    Delete(usize, usize),
+    Insert(usize, &'static str),
+    Truncate(usize),
+    /// Replaces one of the numbers, keeping the rest of the input well-formed.
+    Number(usize, &'static str),
+}
+
+fn edits() -> impl Strategy<Value = Vec<Edit>> {
+    let edit = prop_oneof![
+        (any::<usize>(), 1..20usize).prop_map(|(at, len)| Edit::Delete(at, len)),
+        (any::<usize>(), prop::sample::select(FRAGMENTS)).prop_map(|(at, f)| Edit::Insert(at, f)),
+        any::<usize>().prop_map(Edit::Truncate),
+        (any::<usize>(), prop::sample::select(NUMBERS)).prop_map(|(n, x)| Edit::Number(n, x)),
+    ];
+    prop::collection::vec(edit, 1..8)
+}
+
+/// Applies `edits` to `text`, on character boundaries so the result stays a `&str`.
+fn mangle(text: &str, edits: &[Edit]) -> String {
+    let mut chars: Vec<char> = text.chars().collect();
+    for edit in edits {
+        let len = chars.len() + 1;
+        match *edit {
+            Edit::Delete(at, n) => {
+                let at = at % len;
+                chars.drain(at..(at + n).min(chars.len()));
+            }
+            Edit::Insert(at, fragment) => {
+                let at = at % len;
+                chars.splice(at..at, fragment.chars());
+            }
+            Edit::Truncate(at) => chars.truncate(at % len),
+            Edit::Number(n, number) => {
+                let starts: Vec<usize> = (0..chars.len())
+                    .filter(|&i| {
+                        chars[i].is_ascii_digit() && (i == 0 || !chars[i - 1].is_ascii_digit())
+                    })
+                    .collect();
+                if starts.is_empty() {
+                    continue;
+                }
+                let start = starts[n % starts.len()];
+                let end = (start..chars.len())
+                    .find(|&i| !chars[i].is_ascii_digit())
+                    .unwrap_or(chars.len());
+                chars.splice(start..end, number.chars());
+            }
+        }
+    }
+    chars.into_iter().collect()
+}
+
+/// Parses `input` and, if the parser accepts it, solves both parts.
+fn assert_no_panic(solution: &dyn Runner, input: &str) -> Result<(), TestCaseError> {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        if let Ok(parsed) = solution.parse(input) {
+            for part in Part::ALL {
+                solution.solve(parsed.as_ref(), part);
+            }
+        }
+    }));
+    prop_assert!(
+        result.is_ok(),
+        "day {} panicked on {:?}",
+        solution.day(),
+        input
+    );
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(2000))]
+
+    #[test]
+    fn prop_mangled_samples(day in 0..registry::SOLUTIONS.len(), edits in edits()) {
+        let solution = registry::SOLUTIONS[day];
+        let sample = registry::day_dir(solution.day()).join("sample");
+        let text = std::fs::read_to_string(sample).unwrap();
+        assert_no_panic(solution, &mangle(&text, &edits))?;
+    }
+
+    #[test]
+    fn prop_arbitrary_text(day in 0..registry::SOLUTIONS.len(), input in "\\PC{0,200}") {
+        assert_no_panic(registry::SOLUTIONS[day], &input)?;
+    }
+}
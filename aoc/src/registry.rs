@@ -0,0 +1,60 @@
+use aoc_common::Runner;
+use std::path::PathBuf;
+
+/// Every solved day, in order.
+pub static SOLUTIONS: &[&dyn Runner] = &[
+    &day01::Day01,
+    &day02::Day02,
+    &day03::Day03,
+    &day04::Day04,
+    &day05::Day05,
+    &day06::Day06,
+    &day07::Day07,
+    &day08::Day08,
+    &day09::Day09,
+    &day10::Day10,
+    &day11::Day11,
+    &day12::Day12,
+    &day14::Day14,
+];
+
+pub fn find(day: u8) -> Option<&'static dyn Runner> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
+
+/// Directory of a day's crate, e.g. `07-day7` for day 7.
+pub fn day_dir(day: u8) -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join(format!("{:02}-day{}", day, day))
+}
+
+/// The committed `input` file of a day.
+pub fn default_input(day: u8) -> PathBuf {
+    day_dir(day).join("input")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_is_ordered() {
+        let days: Vec<u8> = SOLUTIONS.iter().map(|s| s.day()).collect();
+        let mut sorted = days.clone();
+        sorted.sort_unstable();
+        sorted.dedup();
+        assert_eq!(days, sorted);
+    }
+
+    #[test]
+    fn test_day_dirs_exist() {
+        for s in SOLUTIONS {
+            assert!(
+                day_dir(s.day()).is_dir(),
+                "missing crate for day {}",
+                s.day()
+            );
+        }
+    }
+}
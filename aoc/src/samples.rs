@@ -0,0 +1,26 @@
+//! Tests for every example input of every day. Each `sample*` file next to a day's `input`
+//! that has a `<sample>.expected` file (see [`answers::parse_expected`]) gets one test per
+//! recorded part, e.g. `day09_sample2_part2`, so adding an example needs no code.
+
+use crate::answers;
+use crate::registry;
+use aoc_common::Part;
+
+/// Solves `part` of `sample` in the directory of `day` and compares it to the sidecar.
+fn check(day: u8, sample: &str, part: Part) {
+    let solution = registry::find(day).expect("day is not registered");
+    let path = registry::day_dir(day).join(sample);
+    let input = std::fs::read_to_string(&path).expect("could not read the sample");
+    let sidecar = path.with_file_name(format!("{}.expected", sample));
+    let expected = answers::parse_expected(&std::fs::read_to_string(&sidecar).unwrap())
+        .unwrap_or_else(|e| panic!("{}: {}", sidecar.display(), e));
+    let parsed = solution
+        .parse(&input)
+        .unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+    assert_eq!(
+        Some(&solution.solve(parsed.as_ref(), part)),
+        expected.get(&part)
+    );
+}
+
+include!(concat!(env!("OUT_DIR"), "/sample_tests.rs"));
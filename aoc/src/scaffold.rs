@@ -0,0 +1,317 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Creates the crate for a new day below the workspace `root` and registers it with the
+/// workspace and the runner. Returns the files that were created or changed.
+pub fn new_day(root: &Path, day: u8) -> Result<Vec<PathBuf>, String> {
+    let dir = root.join(format!("{:02}-day{}", day, day));
+    if dir.exists() {
+        return Err(format!("{} already exists", dir.display()));
+    }
+
+    // Check all registrations before touching anything, so a failure leaves no half-made day.
+    let edits: Vec<(PathBuf, String)> = vec![
+        (
+            root.join("Cargo.toml"),
+            register_in_workspace(&read(&root.join("Cargo.toml"))?, day)?,
+        ),
+        (
+            root.join("aoc").join("Cargo.toml"),
+            register_in_runner_manifest(&read(&root.join("aoc").join("Cargo.toml"))?, day)?,
+        ),
+        (
+            root.join("aoc").join("src").join("registry.rs"),
+            register_in_registry(
+                &read(&root.join("aoc").join("src").join("registry.rs"))?,
+                day,
+            )?,
+        ),
+        (
+            root.join("fuzz").join("Cargo.toml"),
+            register_in_fuzz(&read(&root.join("fuzz").join("Cargo.toml"))?, day)?,
+        ),
+    ];
+
+    let files: Vec<(PathBuf, String)> = vec![
+        (dir.join("Cargo.toml"), crate_manifest(day)),
+        (dir.join("src").join("lib.rs"), lib_rs(day)),
+        (dir.join("src").join("main.rs"), main_rs(day)),
+        (dir.join("sample"), String::new()),
+        (dir.join("sample.expected"), sample_expected()),
+        (
+            root.join("fuzz")
+                .join("fuzz_targets")
+                .join(format!("day{:02}.rs", day)),
+            fuzz_target(day),
+        ),
+    ];
+    for dir in [dir.join("src"), root.join("fuzz").join("fuzz_targets")] {
+        fs::create_dir_all(&dir)
+            .map_err(|e| format!("Could not create {}: {}", dir.display(), e))?;
+    }
+
+    let mut touched: Vec<PathBuf> = Vec::new();
+    for (path, text) in files.into_iter().chain(edits) {
+        fs::write(&path, text).map_err(|e| format!("Could not write {}: {}", path.display(), e))?;
+        touched.push(path);
+    }
+    Ok(touched)
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("Could not read {}: {}", path.display(), e))
+}
+
+/// Adds the day to the workspace `members` and `[workspace.dependencies]`.
+fn register_in_workspace(manifest: &str, day: u8) -> Result<String, String> {
+    let manifest = insert_line(manifest, &format!("\"{:02}-day{}\",", day, day), |l| {
+        l.starts_with('"') && l.contains("-day")
+    })?;
+    insert_line(
+        &manifest,
+        &format!("day{:02} = {{ path = \"{:02}-day{}\" }}", day, day, day),
+        |l| l.starts_with("day") && l.contains(" = { path"),
+    )
+}
+
+/// Adds the day to the runner's dependencies.
+fn register_in_runner_manifest(manifest: &str, day: u8) -> Result<String, String> {
+    insert_line(manifest, &format!("day{:02}.workspace = true", day), |l| {
+        l.starts_with("day") && l.ends_with(".workspace = true")
+    })
+}
+
+/// Adds the day to `SOLUTIONS`.
+fn register_in_registry(registry: &str, day: u8) -> Result<String, String> {
+    insert_line(registry, &format!("&day{:02}::Day{:02},", day, day), |l| {
+        l.starts_with("&day")
+    })
+}
+
+/// Adds the day's parser as a fuzz target.
+fn register_in_fuzz(manifest: &str, day: u8) -> Result<String, String> {
+    let mut manifest = insert_line(
+        manifest,
+        &format!("day{:02} = {{ path = \"../{:02}-day{}\" }}", day, day, day),
+        |l| l.starts_with("day") && l.contains(" = { path"),
+    )?;
+    manifest.push_str(&format!(
+        "\n[[bin]]\nname = \"day{day:02}\"\npath = \"fuzz_targets/day{day:02}.rs\"\ntest = false\ndoc = false\nbench = false\n",
+        day = day
+    ));
+    Ok(manifest)
+}
+
+/// Inserts `new` into the sorted block of lines picked out by `is_peer` (compared without
+/// indentation), indented like its peers.
+fn insert_line<F>(text: &str, new: &str, is_peer: F) -> Result<String, String>
+where
+    F: Fn(&str) -> bool,
+{
+    let lines: Vec<&str> = text.lines().collect();
+    let peers: Vec<usize> = (0..lines.len())
+        .filter(|&i| is_peer(lines[i].trim()))
+        .collect();
+    let (Some(&first), Some(&last)) = (peers.first(), peers.last()) else {
+        return Err(format!("found no place to add `{}`", new));
+    };
+    if peers.iter().any(|&i| lines[i].trim() == new) {
+        return Err(format!("`{}` is already there", new));
+    }
+    let indent: &str = &lines[first][..lines[first].len() - lines[first].trim_start().len()];
+    let at: usize = peers
+        .iter()
+        .copied()
+        .find(|&i| lines[i].trim() > new)
+        .unwrap_or(last + 1);
+
+    let mut out: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+    out.insert(at, format!("{}{}", indent, new));
+    let mut out = out.join("\n");
+    if text.ends_with('\n') {
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn crate_manifest(day: u8) -> String {
+    format!(
+        r#"[package]
+name = "day{:02}"
+version.workspace = true
+edition.workspace = true
+
+[dependencies]
+aoc-common.workspace = true
+"#,
+        day
+    )
+}
+
+fn main_rs(day: u8) -> String {
+    format!(
+        r#"use aoc_common::solution;
+use day{day:02}::Day{day:02};
+use std::process::ExitCode;
+
+fn main() -> ExitCode {{
+    solution::main(&Day{day:02}, concat!(env!("CARGO_MANIFEST_DIR"), "/input"))
+}}
+"#,
+        day = day
+    )
+}
+
+fn fuzz_target(day: u8) -> String {
+    format!(
+        r#"#![no_main]
+
+use aoc_common::Solution;
+use day{day:02}::Day{day:02};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {{
+    if let Ok(parsed) = Day{day:02}::parse(input) {{
+        Day{day:02}::part1(&parsed);
+        Day{day:02}::part2(&parsed);
+    }}
+}});
+"#,
+        day = day
+    )
+}
+
+/// What the parts of a new day answer until they are solved, so that running every day,
+/// `verify` and `bench` still work.
+const UNSOLVED: &str = "unsolved";
+
+fn lib_rs(day: u8) -> String {
+    format!(
+        r#"use aoc_common::{{input, Answer, ParseError, Solution}};
+
+pub struct Day{day:02};
+
+impl Solution for Day{day:02} {{
+    const DAY: u8 = {day};
+    type Parsed = Vec<String>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {{
+        parse_input(input)
+    }}
+
+    fn part1(parsed: &Self::Parsed) -> Answer {{
+        part1(parsed).into()
+    }}
+
+    fn part2(parsed: &Self::Parsed) -> Answer {{
+        part2(parsed).into()
+    }}
+}}
+
+fn parse_input(input: &str) -> Result<Vec<String>, ParseError> {{
+    Ok(input::lines(input))
+}}
+
+fn part1(_input: &Vec<String>) -> String {{
+    "{unsolved}".to_string()
+}}
+
+fn part2(_input: &Vec<String>) -> String {{
+    "{unsolved}".to_string()
+}}
+"#,
+        day = day,
+        unsolved = UNSOLVED
+    )
+}
+
+/// The example's answers as the stub parts give them, so that its generated tests pass until
+/// a part is solved and fail until its answer is recorded.
+fn sample_expected() -> String {
+    format!("part1 = \"{}\"\npart2 = \"{}\"\n", UNSOLVED, UNSOLVED)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{Answer, Part};
+
+    #[test]
+    fn test_insert_line() {
+        let text = "[\n    \"01-day1\",\n    \"14-day14\",\n]\n";
+        let is_member = |l: &str| l.starts_with('"');
+        assert_eq!(
+            insert_line(text, "\"13-day13\",", is_member).unwrap(),
+            "[\n    \"01-day1\",\n    \"13-day13\",\n    \"14-day14\",\n]\n"
+        );
+        assert_eq!(
+            insert_line(text, "\"15-day15\",", is_member).unwrap(),
+            "[\n    \"01-day1\",\n    \"14-day14\",\n    \"15-day15\",\n]\n"
+        );
+        assert!(insert_line(text, "\"14-day14\",", is_member).is_err());
+        assert!(insert_line(text, "x", |l| l.starts_with('x')).is_err());
+    }
+
+    #[test]
+    fn test_registers_with_the_committed_files() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+        let workspace = register_in_workspace(&read(&root.join("Cargo.toml")).unwrap(), 13);
+        assert!(workspace
+            .unwrap()
+            .contains("    \"12-day12\",\n    \"13-day13\",\n    \"14-day14\",\n"));
+        let runner = read(&root.join("aoc").join("Cargo.toml")).unwrap();
+        assert!(register_in_runner_manifest(&runner, 13)
+            .unwrap()
+            .contains("day12.workspace = true\nday13.workspace = true\nday14.workspace = true\n"));
+        let registry = read(&root.join("aoc").join("src").join("registry.rs")).unwrap();
+        assert!(register_in_registry(&registry, 13)
+            .unwrap()
+            .contains("    &day13::Day13,\n    &day14::Day14,\n"));
+        assert!(register_in_registry(&registry, 12).is_err());
+    }
+
+    #[test]
+    fn test_fuzz_targets_follow_the_template() {
+        let targets = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("../fuzz/fuzz_targets");
+        for day in [1, 5, 14] {
+            let target = read(&targets.join(format!("day{:02}.rs", day))).unwrap();
+            assert_eq!(target, fuzz_target(day), "day {}", day);
+        }
+    }
+
+    #[test]
+    fn test_new_day() {
+        let root = std::env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        let repo = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..");
+        fs::create_dir_all(root.join("aoc").join("src")).unwrap();
+        fs::create_dir_all(root.join("fuzz")).unwrap();
+        for file in [
+            "Cargo.toml",
+            "aoc/Cargo.toml",
+            "aoc/src/registry.rs",
+            "fuzz/Cargo.toml",
+        ] {
+            fs::copy(repo.join(file), root.join(file)).unwrap();
+        }
+
+        let touched = new_day(&root, 15).unwrap();
+        assert_eq!(touched.len(), 10);
+        let lib = read(&root.join("15-day15").join("src").join("lib.rs")).unwrap();
+        assert!(lib.contains("impl Solution for Day15 {\n    const DAY: u8 = 15;"));
+        assert!(lib.contains("fn part2(_input: &Vec<String>) -> String {\n    \"unsolved\""));
+        let expected = read(&root.join("15-day15").join("sample.expected")).unwrap();
+        assert_eq!(
+            crate::answers::parse_expected(&expected).unwrap()[&Part::One],
+            Answer::Text("unsolved".to_string())
+        );
+        assert!(read(&root.join("aoc").join("src").join("registry.rs"))
+            .unwrap()
+            .contains("    &day14::Day14,\n    &day15::Day15,\n];"));
+        let fuzz = read(&root.join("fuzz").join("Cargo.toml")).unwrap();
+        assert!(fuzz.contains("day15 = { path = \"../15-day15\" }\n"));
+        assert!(fuzz.ends_with("[[bin]]\nname = \"day15\"\npath = \"fuzz_targets/day15.rs\"\ntest = false\ndoc = false\nbench = false\n"));
+        assert!(new_day(&root, 15).is_err());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
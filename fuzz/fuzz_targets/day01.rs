@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day01::Day01;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(parsed) = Day01::parse(input) {
+        Day01::part1(&parsed);
+        Day01::part2(&parsed);
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day02::Day02;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(parsed) = Day02::parse(input) {
+        Day02::part1(&parsed);
+        Day02::part2(&parsed);
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day03::Day03;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(parsed) = Day03::parse(input) {
+        Day03::part1(&parsed);
+        Day03::part2(&parsed);
+    }
+});
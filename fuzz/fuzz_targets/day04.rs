@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day04::Day04;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(parsed) = Day04::parse(input) {
+        Day04::part1(&parsed);
+        Day04::part2(&parsed);
+    }
+});
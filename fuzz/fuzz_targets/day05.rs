@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day05::Day05;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(parsed) = Day05::parse(input) {
+        Day05::part1(&parsed);
+        Day05::part2(&parsed);
+    }
+});
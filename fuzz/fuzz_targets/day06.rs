@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day06::Day06;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(parsed) = Day06::parse(input) {
+        Day06::part1(&parsed);
+        Day06::part2(&parsed);
+    }
+});
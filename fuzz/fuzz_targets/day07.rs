@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day07::Day07;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(parsed) = Day07::parse(input) {
+        Day07::part1(&parsed);
+        Day07::part2(&parsed);
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day08::Day08;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(parsed) = Day08::parse(input) {
+        Day08::part1(&parsed);
+        Day08::part2(&parsed);
+    }
+});
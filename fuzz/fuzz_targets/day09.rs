@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day09::Day09;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(parsed) = Day09::parse(input) {
+        Day09::part1(&parsed);
+        Day09::part2(&parsed);
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day10::Day10;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(parsed) = Day10::parse(input) {
+        Day10::part1(&parsed);
+        Day10::part2(&parsed);
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day11::Day11;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(parsed) = Day11::parse(input) {
+        Day11::part1(&parsed);
+        Day11::part2(&parsed);
+    }
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day12::Day12;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(parsed) = Day12::parse(input) {
+        Day12::part1(&parsed);
+        Day12::part2(&parsed);
+    }
+});
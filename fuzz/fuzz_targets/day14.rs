@@ -0,0 +1,12 @@
+#![no_main]
+
+use aoc_common::Solution;
+use day14::Day14;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|input: &str| {
+    if let Ok(parsed) = Day14::parse(input) {
+        Day14::part1(&parsed);
+        Day14::part2(&parsed);
+    }
+});